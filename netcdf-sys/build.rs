@@ -137,6 +137,14 @@ impl NcMetaHeader {
                 "DAP requested but not found in this installation of netCDF"
             );
         }
+        if self.has_szip_write {
+            println!("cargo:rustc-cfg=feature=\"has-szip\"");
+            println!("cargo:has-szip=1");
+        }
+        if self.has_parallel {
+            println!("cargo:rustc-cfg=feature=\"has-parallel\"");
+            println!("cargo:has-parallel=1");
+        }
         if self.has_mmap {
             println!("cargo:rustc-cfg=feature=\"has-mmap\"");
             println!("cargo:has-mmap=1");
@@ -305,6 +313,10 @@ fn main() {
                 "cargo:rustc-cfg=feature=\"{}.{}.{}\"",
                 version.major, version.minor, version.patch
             );
+            println!(
+                "cargo:version_{}_{}_{}=1",
+                version.major, version.minor, version.patch
+            );
         }
     }
     metaheader.emit_feature_flags();
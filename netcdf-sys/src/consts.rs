@@ -204,9 +204,21 @@ pub const NC_UNKNOWN_STORAGE: c_int = 3;
 #[cfg(feature = "4.8.1")]
 pub const NC_VIRTUAL: c_int = 4;
 
+pub const NC_SZIP_NN: c_int = 32;
+pub const NC_SZIP_EC: c_int = 4;
+
 pub const NC_NOCHECKSUM: c_int = 0;
 pub const NC_FLETCHER32: c_int = 1;
 
+#[cfg(feature = "4.9.0")]
+pub const NC_NOQUANTIZE: c_int = 0;
+#[cfg(feature = "4.9.0")]
+pub const NC_QUANTIZE_BITGROOM: c_int = 1;
+#[cfg(feature = "4.9.0")]
+pub const NC_QUANTIZE_GRANULARBR: c_int = 2;
+#[cfg(feature = "4.9.0")]
+pub const NC_QUANTIZE_BITROUND: c_int = 3;
+
 pub const NC_NOSHUFFLE: c_int = 0;
 pub const NC_SHUFFLE: c_int = 1;
 
@@ -332,6 +332,20 @@ extern "C" {
         options_maskp: *mut c_int,
         pixels_per_blockp: *mut c_int,
     ) -> c_int;
+    #[cfg(feature = "4.9.0")]
+    pub fn nc_def_var_quantize(
+        ncid: c_int,
+        varid: c_int,
+        quantize_mode: c_int,
+        nsd: c_int,
+    ) -> c_int;
+    #[cfg(feature = "4.9.0")]
+    pub fn nc_inq_var_quantize(
+        ncid: c_int,
+        varid: c_int,
+        quantize_modep: *mut c_int,
+        nsdp: *mut c_int,
+    ) -> c_int;
     pub fn nc_def_var_fletcher32(ncid: c_int, varid: c_int, fletcher32: c_int) -> c_int;
     pub fn nc_inq_var_fletcher32(ncid: c_int, varid: c_int, fletcher32p: *mut c_int) -> c_int;
     pub fn nc_def_var_chunking(
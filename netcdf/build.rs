@@ -1,5 +1,20 @@
 fn main() {
-    if std::env::var("DEP_NETCDF_SYS_HAS_MMAP").is_ok() {
+    if std::env::var("DEP_NETCDF_HAS_MMAP").is_ok() {
         println!("cargo:rustc-cfg=feature=\"has-mmap\"");
     }
+    if std::env::var("DEP_NETCDF_HAS_DAP").is_ok() {
+        println!("cargo:rustc-cfg=feature=\"has-dap\"");
+    }
+    if std::env::var("DEP_NETCDF_HAS_PARALLEL").is_ok() {
+        println!("cargo:rustc-cfg=feature=\"has-parallel\"");
+    }
+    if std::env::var("DEP_NETCDF_HAS_SZIP").is_ok() {
+        println!("cargo:rustc-cfg=feature=\"has-szip\"");
+    }
+    // Versions of the underlying library, exported by `netcdf-sys`
+    for (key, _value) in std::env::vars() {
+        if let Some(version) = key.strip_prefix("DEP_NETCDF_VERSION_") {
+            println!("cargo:rustc-cfg=feature=\"{}\"", version.replace('_', "."));
+        }
+    }
 }
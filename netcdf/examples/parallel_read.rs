@@ -0,0 +1,60 @@
+//! Compares reading two independent files sequentially and in parallel.
+//!
+//! Without the `thread-safe-hdf5` feature all calls are serialized by the
+//! global lock, and the parallel read takes as long as the sequential one.
+//! With the feature both files opt in to concurrent reads
+use std::time::{Duration, Instant};
+
+const LEN: usize = 4_000_000;
+const REPEATS: usize = 10;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let paths = [dir.path().join("first.nc"), dir.path().join("second.nc")];
+
+    for path in &paths {
+        let mut file = netcdf::create(path)?;
+        file.add_dimension("x", LEN)?;
+        let mut var = file.add_variable::<f64>("data", &["x"])?;
+        let data = (0..LEN).map(|i| i as f64).collect::<Vec<_>>();
+        var.put_values(&data, ..)?;
+    }
+
+    let sequential = time(|| {
+        for path in &paths {
+            read(path);
+        }
+    });
+    let parallel = time(|| {
+        std::thread::scope(|s| {
+            for path in &paths {
+                s.spawn(move || read(path));
+            }
+        });
+    });
+
+    println!("sequential: {sequential:?}");
+    println!("parallel:   {parallel:?}");
+    Ok(())
+}
+
+fn read(path: &std::path::Path) {
+    let file = netcdf::open(path).unwrap();
+    #[cfg(feature = "thread-safe-hdf5")]
+    // Only reads of variable data run in parallel here, which is assumed
+    // to be supported by the linked netCDF-c
+    if let Err(e) = unsafe { file.enable_concurrent_reads() } {
+        eprintln!("reads are serialized: {e}");
+    }
+    let var = file.variable("data").unwrap();
+    let mut buffer = vec![0.0_f64; LEN];
+    for _ in 0..REPEATS {
+        var.values_to(&mut buffer, ..).unwrap();
+    }
+}
+
+fn time(f: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
@@ -47,8 +47,17 @@ impl<'a> Attribute<'a> {
         std::str::from_utf8(&self.name[..zeropos])
             .expect("Attribute name contains invalid sequence")
     }
-    /// Number of elements in this attribute
-    fn num_elems(&self) -> error::Result<usize> {
+    /// Number of elements in this attribute, without reading the values
+    ///
+    /// A length of one is returned as a scalar by [`value`](Self::value),
+    /// other lengths as a `Vec`. Text attributes of type
+    /// [`AttrType::Char`] are a char array, and their length is the
+    /// number of bytes in the string
+    ///
+    /// # Errors
+    ///
+    /// netcdf error
+    pub fn len(&self) -> error::Result<usize> {
         let mut nelems = 0;
         unsafe {
             error::checked(super::with_lock(|| {
@@ -62,6 +71,22 @@ impl<'a> Attribute<'a> {
         }
         Ok(nelems as _)
     }
+    /// The attribute has no elements
+    ///
+    /// # Errors
+    ///
+    /// netcdf error
+    pub fn is_empty(&self) -> error::Result<bool> {
+        self.len().map(|len| len == 0)
+    }
+    /// Type of the values in this attribute, without reading the values
+    ///
+    /// # Errors
+    ///
+    /// netcdf error
+    pub fn attr_type(&self) -> error::Result<AttrType> {
+        Ok(AttrType::from_nctype(self.typ()?))
+    }
     /// Type of this attribute
     fn typ(&self) -> error::Result<nc_type> {
         let mut atttype = 0;
@@ -77,6 +102,59 @@ impl<'a> Attribute<'a> {
         }
         Ok(atttype)
     }
+    /// Get the value of the attribute as a concrete type
+    ///
+    /// The type must match the type stored in the file, and scalars
+    /// can only be read from attributes containing a single value.
+    /// Use a `Vec` to read all values of the attribute.
+    ///
+    /// # Errors
+    ///
+    /// Mismatched type, or netcdf error
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// let scale_factor = var.attribute("scale_factor").unwrap().value_as::<f64>()?;
+    /// let valid_range = var.attribute("valid_range").unwrap().value_as::<Vec<i32>>()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn value_as<T>(&self) -> error::Result<T>
+    where
+        T: TryFrom<AttrValue, Error = error::Error>,
+    {
+        self.value()?.try_into()
+    }
+    /// Get the value of a text attribute, whether it is stored
+    /// as a char array (classic files) or as a string (`netCDF-4`)
+    ///
+    /// # Errors
+    ///
+    /// Not a text attribute, the attribute holds more than
+    /// one string, or netcdf error
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let title = file.attribute("title").unwrap().text()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn text(&self) -> error::Result<String> {
+        if !matches!(self.attr_type()?, AttrType::Char | AttrType::String) {
+            return Err(error::Error::TypeMismatch);
+        }
+        match self.value()? {
+            AttrValue::Str(s) => Ok(s),
+            AttrValue::Strs(mut strs) if strs.len() <= 1 => Ok(strs.pop().unwrap_or_default()),
+            AttrValue::Strs(strs) => {
+                Err(format!("attribute {} contains {} strings", self.name(), strs.len()).into())
+            }
+            _ => Err(error::Error::TypeMismatch),
+        }
+    }
     /// Get the value of the attribute
     ///
     /// # Errors
@@ -84,7 +162,7 @@ impl<'a> Attribute<'a> {
     /// Unsupported type or netcdf error
     #[allow(clippy::too_many_lines)]
     pub fn value(&self) -> error::Result<AttrValue> {
-        let attlen = self.num_elems()?;
+        let attlen = self.len()?;
         let typ = self.typ()?;
 
         match typ {
@@ -500,10 +578,101 @@ impl<'a> Iterator for AttributeIterator<'a> {
     }
 }
 
+/// Read the names and values of all attributes at a location
+pub(crate) fn attribute_map(
+    ncid: nc_type,
+    varid: Option<nc_type>,
+) -> error::Result<Vec<(String, AttrValue)>> {
+    AttributeIterator::new(ncid, varid)?
+        .map(|attr| {
+            let attr = attr?;
+            Ok((attr.name().to_owned(), attr.value()?))
+        })
+        .collect()
+}
+
+/// Type of the values in an attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttrType {
+    /// `u8`
+    Uchar,
+    /// `i8`
+    Schar,
+    /// `u16`
+    Ushort,
+    /// `i16`
+    Short,
+    /// `u32`
+    Uint,
+    /// `i32`
+    Int,
+    /// `u64`
+    Ulonglong,
+    /// `i64`
+    Longlong,
+    /// `f32`
+    Float,
+    /// `f64`
+    Double,
+    /// Array of characters, read as a single string
+    Char,
+    /// Variable length strings
+    String,
+    /// User defined type with this id, not readable as an [`AttrValue`]
+    UserDefined(nc_type),
+}
+
+impl AttrType {
+    fn from_nctype(xtype: nc_type) -> Self {
+        match xtype {
+            NC_UBYTE => Self::Uchar,
+            NC_BYTE => Self::Schar,
+            NC_USHORT => Self::Ushort,
+            NC_SHORT => Self::Short,
+            NC_UINT => Self::Uint,
+            NC_INT => Self::Int,
+            NC_UINT64 => Self::Ulonglong,
+            NC_INT64 => Self::Longlong,
+            NC_FLOAT => Self::Float,
+            NC_DOUBLE => Self::Double,
+            NC_CHAR => Self::Char,
+            NC_STRING => Self::String,
+            x => Self::UserDefined(x),
+        }
+    }
+}
+
 /// Holds the attribute value which can be inserted and
 /// returned from the file
+///
+/// Values can be converted to Rust types with `TryFrom`, which
+/// only allows lossless conversions:
+/// * integers convert to any integer type, failing with
+///   [`Conversion`](error::Error::Conversion) if the value is out of range
+/// * `f64` accepts floats and integers of up to 32 bits,
+///   `f32` accepts `f32` and integers of up to 16 bits
+/// * strings only convert to `String`
+/// * a `Vec` also accepts a single value
+///
+/// Other conversions fail with [`TypeMismatch`](error::Error::TypeMismatch)
+/// ```
+/// # fn main() -> Result<(), netcdf::error::Error> {
+/// use netcdf::AttrValue;
+/// let x: f64 = AttrValue::Int(3).try_into()?;
+/// let x: Vec<u8> = AttrValue::Shorts(vec![1, 2]).try_into()?;
+/// let x: Result<u8, _> = AttrValue::Int(300).try_into();
+/// assert!(x.is_err());
+/// let x: Result<i64, _> = AttrValue::Double(1.0).try_into();
+/// assert!(x.is_err());
+/// # Ok(()) }
+/// ```
+///
+/// With the `serde` feature the value is serialized
+/// tagged with the variant, preserving the type
 #[allow(missing_docs)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttrValue {
     Uchar(u8),
     Uchars(Vec<u8>),
@@ -529,6 +698,31 @@ pub enum AttrValue {
     Strs(Vec<String>),
 }
 
+impl AttrValue {
+    /// Type of the attribute written for this value, `Str` gives
+    /// [`AttrType::Char`] and `Strs` gives [`AttrType::String`]
+    pub fn attr_type(&self) -> AttrType {
+        AttrType::from_nctype(self.nctype())
+    }
+    /// Type of the attribute written for this value
+    fn nctype(&self) -> nc_type {
+        match self {
+            Self::Uchar(_) | Self::Uchars(_) => NC_UBYTE,
+            Self::Schar(_) | Self::Schars(_) => NC_BYTE,
+            Self::Ushort(_) | Self::Ushorts(_) => NC_USHORT,
+            Self::Short(_) | Self::Shorts(_) => NC_SHORT,
+            Self::Uint(_) | Self::Uints(_) => NC_UINT,
+            Self::Int(_) | Self::Ints(_) => NC_INT,
+            Self::Ulonglong(_) | Self::Ulonglongs(_) => NC_UINT64,
+            Self::Longlong(_) | Self::Longlongs(_) => NC_INT64,
+            Self::Float(_) | Self::Floats(_) => NC_FLOAT,
+            Self::Double(_) | Self::Doubles(_) => NC_DOUBLE,
+            Self::Str(_) => NC_CHAR,
+            Self::Strs(_) => NC_STRING,
+        }
+    }
+}
+
 impl<'a> Attribute<'a> {
     #[allow(clippy::needless_pass_by_value)] // All values will be small
     #[allow(clippy::too_many_lines)]
@@ -748,6 +942,62 @@ impl<'a> Attribute<'a> {
             _marker: PhantomData,
         }))
     }
+
+    /// Remove the attribute `name`, reentering define mode if necessary
+    pub(crate) fn delete(ncid: nc_type, varid: nc_type, name: &str) -> error::Result<()> {
+        let cname = super::utils::short_name_to_bytes(name)?;
+        super::utils::checked_in_define_mode(ncid, || unsafe {
+            nc_del_att(ncid, varid, cname.as_ptr().cast())
+        })
+    }
+
+    pub(crate) fn rename(ncid: nc_type, varid: nc_type, old: &str, new: &str) -> error::Result<()> {
+        let cold = super::utils::short_name_to_bytes(old)?;
+        let cnew = super::utils::short_name_to_bytes(new)?;
+        let e = super::utils::in_define_mode(ncid, || unsafe {
+            nc_rename_att(ncid, varid, cold.as_ptr().cast(), cnew.as_ptr().cast())
+        })?;
+        error::checked_name(e, "attribute", new)
+    }
+
+    /// Overwrite or create the attribute `name`, reentering define mode
+    /// if the new value does not fit in the space of the old value.
+    /// With `same_type` an existing attribute must have the type of `val`
+    pub(crate) fn set(
+        ncid: nc_type,
+        varid: nc_type,
+        name: &str,
+        val: AttrValue,
+        same_type: bool,
+    ) -> error::Result<Self> {
+        if same_type {
+            let cname = super::utils::short_name_to_bytes(name)?;
+            let mut atttype = 0;
+            let e = unsafe {
+                super::with_lock(|| {
+                    nc_inq_atttype(ncid, varid, cname.as_ptr().cast(), &mut atttype)
+                })
+            };
+            if e != NC_ENOTATT {
+                error::checked(e)?;
+                if atttype != val.nctype() {
+                    return Err(error::Error::TypeMismatch);
+                }
+            }
+        }
+        match Self::put(ncid, varid, name, val.clone()) {
+            Err(error::Error::Netcdf(NC_ENOTINDEFINE)) => {}
+            ret => return ret,
+        }
+        unsafe {
+            error::checked(super::with_lock(|| nc_redef(ncid)))?;
+        }
+        let ret = Self::put(ncid, varid, name, val);
+        let e = unsafe { super::with_lock(|| nc_enddef(ncid)) };
+        let ret = ret?;
+        error::checked(e)?;
+        Ok(ret)
+    }
 }
 
 // Boring implementations
@@ -882,6 +1132,148 @@ impl From<Vec<&str>> for AttrValue {
     }
 }
 
+macro_rules! impl_from_slice {
+    ($typ: ty, $multiple: ident) => {
+        impl From<&[$typ]> for AttrValue {
+            fn from(x: &[$typ]) -> Self {
+                Self::$multiple(x.to_vec())
+            }
+        }
+        impl<const N: usize> From<[$typ; N]> for AttrValue {
+            fn from(x: [$typ; N]) -> Self {
+                Self::$multiple(x.to_vec())
+            }
+        }
+    };
+}
+
+impl_from_slice!(u8, Uchars);
+impl_from_slice!(i8, Schars);
+impl_from_slice!(u16, Ushorts);
+impl_from_slice!(i16, Shorts);
+impl_from_slice!(u32, Uints);
+impl_from_slice!(i32, Ints);
+impl_from_slice!(u64, Ulonglongs);
+impl_from_slice!(i64, Longlongs);
+impl_from_slice!(f32, Floats);
+impl_from_slice!(f64, Doubles);
+
+macro_rules! impl_try_from_attrvalue_int {
+    ($typ: ty) => {
+        impl TryFrom<AttrValue> for $typ {
+            type Error = error::Error;
+            fn try_from(value: AttrValue) -> Result<Self, Self::Error> {
+                match value {
+                    AttrValue::Uchar(x) => Ok(x.try_into()?),
+                    AttrValue::Schar(x) => Ok(x.try_into()?),
+                    AttrValue::Ushort(x) => Ok(x.try_into()?),
+                    AttrValue::Short(x) => Ok(x.try_into()?),
+                    AttrValue::Uint(x) => Ok(x.try_into()?),
+                    AttrValue::Int(x) => Ok(x.try_into()?),
+                    AttrValue::Ulonglong(x) => Ok(x.try_into()?),
+                    AttrValue::Longlong(x) => Ok(x.try_into()?),
+                    _ => Err(error::Error::TypeMismatch),
+                }
+            }
+        }
+        impl TryFrom<AttrValue> for Vec<$typ> {
+            type Error = error::Error;
+            fn try_from(value: AttrValue) -> Result<Self, Self::Error> {
+                fn convert<T>(values: Vec<T>) -> error::Result<Vec<$typ>>
+                where
+                    T: TryInto<$typ>,
+                    error::Error: From<T::Error>,
+                {
+                    values.into_iter().map(|x| Ok(x.try_into()?)).collect()
+                }
+                match value {
+                    AttrValue::Uchars(x) => convert(x),
+                    AttrValue::Schars(x) => convert(x),
+                    AttrValue::Ushorts(x) => convert(x),
+                    AttrValue::Shorts(x) => convert(x),
+                    AttrValue::Uints(x) => convert(x),
+                    AttrValue::Ints(x) => convert(x),
+                    AttrValue::Ulonglongs(x) => convert(x),
+                    AttrValue::Longlongs(x) => convert(x),
+                    single => Ok(vec![single.try_into()?]),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_attrvalue_int!(u8);
+impl_try_from_attrvalue_int!(i8);
+impl_try_from_attrvalue_int!(u16);
+impl_try_from_attrvalue_int!(i16);
+impl_try_from_attrvalue_int!(u32);
+impl_try_from_attrvalue_int!(i32);
+impl_try_from_attrvalue_int!(u64);
+impl_try_from_attrvalue_int!(i64);
+
+macro_rules! impl_try_from_attrvalue_float {
+    ($typ: ty, $(($single: ident, $multiple: ident)),*) => {
+        impl TryFrom<AttrValue> for $typ {
+            type Error = error::Error;
+            fn try_from(value: AttrValue) -> Result<Self, Self::Error> {
+                match value {
+                    $(AttrValue::$single(x) => Ok(x.into()),)*
+                    _ => Err(error::Error::TypeMismatch),
+                }
+            }
+        }
+        impl TryFrom<AttrValue> for Vec<$typ> {
+            type Error = error::Error;
+            fn try_from(value: AttrValue) -> Result<Self, Self::Error> {
+                match value {
+                    $(AttrValue::$multiple(x) => Ok(x.into_iter().map(Into::into).collect()),)*
+                    single => Ok(vec![single.try_into()?]),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_attrvalue_float!(
+    f32,
+    (Float, Floats),
+    (Uchar, Uchars),
+    (Schar, Schars),
+    (Ushort, Ushorts),
+    (Short, Shorts)
+);
+impl_try_from_attrvalue_float!(
+    f64,
+    (Double, Doubles),
+    (Float, Floats),
+    (Uchar, Uchars),
+    (Schar, Schars),
+    (Ushort, Ushorts),
+    (Short, Shorts),
+    (Uint, Uints),
+    (Int, Ints)
+);
+
+impl TryFrom<AttrValue> for String {
+    type Error = error::Error;
+    fn try_from(value: AttrValue) -> Result<Self, Self::Error> {
+        match value {
+            AttrValue::Str(x) => Ok(x),
+            _ => Err(error::Error::TypeMismatch),
+        }
+    }
+}
+impl TryFrom<AttrValue> for Vec<String> {
+    type Error = error::Error;
+    fn try_from(value: AttrValue) -> Result<Self, Self::Error> {
+        match value {
+            AttrValue::Str(x) => Ok(vec![x]),
+            AttrValue::Strs(x) => Ok(x),
+            _ => Err(error::Error::TypeMismatch),
+        }
+    }
+}
+
 #[test]
 fn conversion() {
     let x = 1.0f32;
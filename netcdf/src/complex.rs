@@ -0,0 +1,231 @@
+//! Complex numbers stored as pairs of floating point numbers
+
+use super::error;
+use super::extent::Extents;
+use super::types::{BasicType, VariableType};
+use super::{Variable, VariableMut};
+use num_complex::Complex;
+
+/// Convention used by a variable for storing complex numbers
+enum Layout {
+    /// Compound type of `size` bytes, with the parts at these offsets
+    Compound {
+        size: usize,
+        real: usize,
+        imag: usize,
+        single: bool,
+    },
+    /// The last dimension holds the parts
+    Pair,
+}
+
+impl Layout {
+    fn of(var: &Variable) -> error::Result<Self> {
+        match var.vartype() {
+            VariableType::Compound(typ) => {
+                let mut real = None;
+                let mut imag = None;
+                let mut nfields = 0;
+                for field in typ.fields() {
+                    nfields += 1;
+                    let single = match (field.typ(), field.dimensions()) {
+                        (VariableType::Basic(BasicType::Float), None) => true,
+                        (VariableType::Basic(BasicType::Double), None) => false,
+                        _ => continue,
+                    };
+                    match field.name().as_str() {
+                        "r" | "real" => real = Some((field.offset(), single)),
+                        "i" | "imag" => imag = Some((field.offset(), single)),
+                        _ => (),
+                    }
+                }
+                match (real, imag) {
+                    (Some((real, single)), Some((imag, single_imag)))
+                        if nfields == 2 && single == single_imag =>
+                    {
+                        Ok(Self::Compound {
+                            size: typ.size(),
+                            real,
+                            imag,
+                            single,
+                        })
+                    }
+                    _ => Err(not_complex(var)),
+                }
+            }
+            VariableType::Basic(typ) if typ != BasicType::Char => match var.dimensions.last() {
+                Some(dim) if dim.len() == 2 => Ok(Self::Pair),
+                _ => Err(not_complex(var)),
+            },
+            _ => Err(not_complex(var)),
+        }
+    }
+}
+
+fn not_complex(var: &Variable) -> error::Error {
+    format!(
+        "variable {} is neither a compound of floats r and i, \
+         nor has a last dimension of length 2",
+        var.name()
+    )
+    .into()
+}
+
+/// Extents of a variable with `extents` for the leading dimensions,
+/// and both elements of the last dimension of length 2
+fn pair_extents(var: &Variable, extents: &Extents) -> error::Result<Extents> {
+    let leading = &var.dimensions[..var.dimensions.len() - 1];
+    let (mut start, mut count, mut stride) = extents.get_start_count_stride(leading)?;
+    start.push(0);
+    count.push(2);
+    stride.push(1);
+    (start, count, stride).try_into()
+}
+
+impl<'g> Variable<'g> {
+    fn complex_values_mono(&self, extents: &Extents) -> error::Result<Vec<Complex<f64>>> {
+        match Layout::of(self)? {
+            Layout::Compound {
+                size,
+                real,
+                imag,
+                single,
+            } => {
+                let (bytes, _typ) = self.values_compound_raw(extents)?;
+                let part = |elem: &[u8], offset: usize| {
+                    if single {
+                        let mut b = [0; 4];
+                        b.copy_from_slice(&elem[offset..offset + 4]);
+                        f64::from(f32::from_ne_bytes(b))
+                    } else {
+                        let mut b = [0; 8];
+                        b.copy_from_slice(&elem[offset..offset + 8]);
+                        f64::from_ne_bytes(b)
+                    }
+                };
+                Ok(bytes
+                    .chunks_exact(size)
+                    .map(|elem| Complex::new(part(elem, real), part(elem, imag)))
+                    .collect())
+            }
+            Layout::Pair => {
+                let values = self.values::<f64, _>(pair_extents(self, extents)?)?;
+                Ok(values
+                    .chunks_exact(2)
+                    .map(|pair| Complex::new(pair[0], pair[1]))
+                    .collect())
+            }
+        }
+    }
+    /// Get complex values
+    ///
+    /// `netCDF` has no complex type, and two conventions are supported:
+    ///
+    /// * A compound type with the two fields `r` and `i` (or `real` and
+    ///   `imag`), both `f32` or both `f64`, as written by `h5py`.
+    ///   `extents` select along all dimensions
+    /// * A numeric variable with a last dimension of length 2, holding
+    ///   the real part at index 0 and the imaginary part at index 1.
+    ///   `extents` select along the other dimensions, and the values
+    ///   are converted to `f64`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("signal.nc")?;
+    /// let var = file.variable("iq").unwrap();
+    /// let first = var.complex_values(..100)?;
+    /// let power = first.iter().map(|z| z.norm_sqr()).sum::<f64>();
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The variable does not follow either convention, invalid `extents`,
+    /// or netcdf error
+    pub fn complex_values<E>(&self, extents: E) -> error::Result<Vec<Complex<f64>>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.complex_values_mono(&extents)
+    }
+}
+
+impl<'g> VariableMut<'g> {
+    fn put_complex_values_mono(
+        &mut self,
+        values: &[Complex<f64>],
+        extents: &Extents,
+    ) -> error::Result<()> {
+        match Layout::of(self)? {
+            Layout::Compound {
+                size,
+                real,
+                imag,
+                single,
+            } => {
+                let mut bytes = vec![0_u8; values.len().saturating_mul(size)];
+                for (elem, value) in bytes.chunks_exact_mut(size).zip(values) {
+                    for (offset, part) in [(real, value.re), (imag, value.im)] {
+                        if single {
+                            #[allow(clippy::cast_possible_truncation)]
+                            let part = part as f32;
+                            elem[offset..offset + 4].copy_from_slice(&part.to_ne_bytes());
+                        } else {
+                            elem[offset..offset + 8].copy_from_slice(&part.to_ne_bytes());
+                        }
+                    }
+                }
+                // Safe as the fields are plain floats
+                unsafe { self.put_raw_values(&bytes, extents) }
+            }
+            Layout::Pair => {
+                let pairs = values
+                    .iter()
+                    .flat_map(|value| [value.re, value.im])
+                    .collect::<Vec<f64>>();
+                let extents = pair_extents(self, extents)?;
+                self.put_values(&pairs, extents)
+            }
+        }
+    }
+    /// Put complex values, using the convention the variable was
+    /// created with, see [`Variable::complex_values`]
+    ///
+    /// `extents` are as for reading. Values are converted to the
+    /// type of the variable, e.g. rounded to `f32`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use num_complex::Complex;
+    /// let mut file = netcdf::create("signal.nc")?;
+    /// file.add_dimension("time", 2)?;
+    /// file.add_dimension("complex", 2)?;
+    /// let mut var = file.add_variable::<f64>("iq", &["time", "complex"])?;
+    /// var.put_complex_values(&[Complex::new(1.0, -1.0), Complex::new(0.0, 2.0)], ..)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The variable does not follow either convention, `values` does not
+    /// match `extents`, the values can not be represented in the type of
+    /// the variable, or netcdf error
+    pub fn put_complex_values<E>(
+        &mut self,
+        values: &[Complex<f64>],
+        extents: E,
+    ) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_complex_values_mono(values, &extents)
+    }
+}
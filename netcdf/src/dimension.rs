@@ -2,6 +2,7 @@
 
 #![allow(clippy::similar_names)]
 use super::error;
+use super::variable::{NcPutGet, Variable};
 use netcdf_sys::*;
 use std::convert::TryInto;
 use std::marker::PhantomData;
@@ -26,6 +27,9 @@ pub struct Identifier {
 #[allow(clippy::len_without_is_empty)]
 impl<'g> Dimension<'g> {
     /// Get current length of this dimension
+    ///
+    /// The length of unlimited dimensions is requeried on every
+    /// call, and grows as data is written along the dimension
     pub fn len(&self) -> usize {
         if let Some(x) = self.len {
             x.get()
@@ -43,7 +47,7 @@ impl<'g> Dimension<'g> {
         }
     }
 
-    /// Checks whether the dimension is growable
+    /// Checks whether the dimension is growable (unlimited)
     pub fn is_unlimited(&self) -> bool {
         self.len.is_none()
     }
@@ -68,6 +72,43 @@ impl<'g> Dimension<'g> {
     pub fn identifier(&self) -> Identifier {
         self.id
     }
+
+    /// Read the values of the coordinate variable of this dimension
+    ///
+    /// Following the CF conventions, this is the one-dimensional
+    /// variable in the same group with the same name as the dimension.
+    /// The values are converted to `T` by `netCDF` if the variable has
+    /// another numeric type
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?;
+    /// let lat = file.dimension("lat").unwrap();
+    /// if let Some(coords) = lat.coordinate_values::<f64>()? {
+    ///     for (i, c) in coords.iter().enumerate() {
+    ///         println!("{i}: {c}");
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The coordinate variable could not be read as `T`
+    pub fn coordinate_values<T: NcPutGet>(&self) -> error::Result<Option<Vec<T>>> {
+        let var = match Variable::find_from_name(self.id.ncid, &self.name())? {
+            Some(var) => var,
+            None => return Ok(None),
+        };
+        match var.dimensions() {
+            [dim] if dim.id.ncid == self.id.ncid && dim.id.dimid == self.id.dimid => {
+                var.values::<T, _>(..).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 pub(crate) fn from_name_toid(loc: nc_type, name: &str) -> error::Result<Option<nc_type>> {
@@ -192,38 +233,89 @@ pub(crate) fn dimensions_from_variable<'g>(
             nc_inq_vardimid(ncid, varid, dimids.as_mut_ptr())
         }))?;
     }
-    let unlimdims = {
-        let mut nunlimdims = 0;
-        unsafe {
-            error::checked(super::with_lock(|| {
-                nc_inq_unlimdims(ncid, &mut nunlimdims, std::ptr::null_mut())
-            }))?;
+
+    // The dimensions may be defined in any of the parent groups,
+    // find the group defining each dimension and whether it is unlimited
+    let mut locations: Vec<Option<(nc_type, bool)>> = vec![None; dimids.len()];
+    let mut grpid = ncid;
+    loop {
+        let defined = dimids_at(grpid)?;
+        let unlimdims = unlimdims_at(grpid)?;
+        for (dimid, location) in dimids.iter().zip(&mut locations) {
+            if location.is_none() && defined.contains(dimid) {
+                *location = Some((grpid, unlimdims.contains(dimid)));
+            }
         }
-        let mut unlimdims = Vec::with_capacity(nunlimdims.try_into()?);
-        unsafe {
-            error::checked(super::with_lock(|| {
-                nc_inq_unlimdims(ncid, std::ptr::null_mut(), unlimdims.as_mut_ptr())
-            }))?;
+        if locations.iter().all(Option::is_some) {
+            break;
         }
-        unsafe {
-            unlimdims.set_len(nunlimdims.try_into()?);
+        let mut parent = 0;
+        let e = unsafe { super::with_lock(|| nc_inq_grp_parent(grpid, &mut parent)) };
+        if e == NC_ENOGRP {
+            break;
         }
-        unlimdims
-    };
+        error::checked(e)?;
+        grpid = parent;
+    }
 
-    Ok(dimids.into_iter().map(move |dimid| {
-        let mut dimlen = 0;
-        if !unlimdims.contains(&dimid) {
-            unsafe {
-                error::checked(super::with_lock(|| nc_inq_dimlen(ncid, dimid, &mut dimlen)))?;
+    Ok(dimids
+        .into_iter()
+        .zip(locations)
+        .map(move |(dimid, location)| {
+            let (grpid, unlimited) = location.unwrap_or((ncid, false));
+            let mut dimlen = 0;
+            if !unlimited {
+                unsafe {
+                    error::checked(super::with_lock(|| {
+                        nc_inq_dimlen(grpid, dimid, &mut dimlen)
+                    }))?;
+                }
             }
-        }
-        Ok(Dimension {
-            len: core::num::NonZeroUsize::new(dimlen),
-            id: Identifier { ncid, dimid },
-            _group: PhantomData,
-        })
-    }))
+            Ok(Dimension {
+                len: core::num::NonZeroUsize::new(dimlen),
+                id: Identifier { ncid: grpid, dimid },
+                _group: PhantomData,
+            })
+        }))
+}
+
+/// Dimensions defined in this group, excluding the parents
+fn dimids_at(ncid: nc_type) -> error::Result<Vec<nc_type>> {
+    let mut ndims = 0;
+    unsafe {
+        error::checked(super::with_lock(|| {
+            nc_inq_dimids(ncid, &mut ndims, std::ptr::null_mut(), <_>::from(false))
+        }))?;
+    }
+    let mut dimids = vec![0; ndims.try_into()?];
+    unsafe {
+        error::checked(super::with_lock(|| {
+            nc_inq_dimids(
+                ncid,
+                std::ptr::null_mut(),
+                dimids.as_mut_ptr(),
+                <_>::from(false),
+            )
+        }))?;
+    }
+    Ok(dimids)
+}
+
+/// Unlimited dimensions defined in this group
+fn unlimdims_at(ncid: nc_type) -> error::Result<Vec<nc_type>> {
+    let mut nunlimdims = 0;
+    unsafe {
+        error::checked(super::with_lock(|| {
+            nc_inq_unlimdims(ncid, &mut nunlimdims, std::ptr::null_mut())
+        }))?;
+    }
+    let mut unlimdims = vec![0; nunlimdims.try_into()?];
+    unsafe {
+        error::checked(super::with_lock(|| {
+            nc_inq_unlimdims(ncid, std::ptr::null_mut(), unlimdims.as_mut_ptr())
+        }))?;
+    }
+    Ok(unlimdims)
 }
 
 pub(crate) fn dimension_from_name<'f>(
@@ -270,6 +362,47 @@ pub(crate) fn dimension_from_name<'f>(
     }))
 }
 
+/// Look up the dimension of `id`, checking that it belongs to the
+/// same file as `ncid`
+pub(crate) fn from_identifier<'f>(
+    ncid: nc_type,
+    id: Identifier,
+) -> error::Result<Option<Dimension<'f>>> {
+    // Internal netcdf detail, the top 16 bits gives the file handle
+    if id.ncid >> 16 != ncid >> 16 {
+        return Ok(None);
+    }
+    let mut dimlen = 0;
+    let e = unsafe { super::with_lock(|| nc_inq_dimlen(id.ncid, id.dimid, &mut dimlen)) };
+    if e == NC_EBADDIM || e == NC_EBADID {
+        return Ok(None);
+    }
+    error::checked(e)?;
+
+    // Unlimited dimensions are only listed in the group defining them,
+    // which could be any of the parents of the identifier group
+    let mut grpid = id.ncid;
+    loop {
+        if unlimdims_at(grpid)?.contains(&id.dimid) {
+            dimlen = 0;
+            break;
+        }
+        let mut parent = 0;
+        let e = unsafe { super::with_lock(|| nc_inq_grp_parent(grpid, &mut parent)) };
+        if e == NC_ENOGRP {
+            break;
+        }
+        error::checked(e)?;
+        grpid = parent;
+    }
+
+    Ok(Some(Dimension {
+        len: core::num::NonZeroUsize::new(dimlen),
+        id,
+        _group: PhantomData,
+    }))
+}
+
 pub(crate) fn add_dimension_at<'f>(
     ncid: nc_type,
     name: &str,
@@ -278,13 +411,25 @@ pub(crate) fn add_dimension_at<'f>(
     let cname = super::utils::short_name_to_bytes(name)?;
     let mut dimid = 0;
     unsafe {
-        error::checked(super::with_lock(|| {
-            nc_def_dim(ncid, cname.as_ptr().cast(), len, &mut dimid)
-        }))?;
+        error::checked_name(
+            super::with_lock(|| nc_def_dim(ncid, cname.as_ptr().cast(), len, &mut dimid)),
+            "dimension",
+            name,
+        )?;
     }
     Ok(Dimension {
-        len: core::num::NonZeroUsize::new(dimid.try_into()?),
+        len: core::num::NonZeroUsize::new(len),
         id: Identifier { ncid, dimid },
         _group: PhantomData,
     })
 }
+
+pub(crate) fn rename_dimension_at(ncid: nc_type, old: &str, new: &str) -> error::Result<()> {
+    let dimid = from_name_toid(ncid, old)?
+        .ok_or_else(|| error::Error::NotFound(format!("dimension {old}")))?;
+    let cname = super::utils::short_name_to_bytes(new)?;
+    let e = super::utils::in_define_mode(ncid, || unsafe {
+        nc_rename_dim(ncid, dimid, cname.as_ptr().cast())
+    })?;
+    error::checked_name(e, "dimension", new)
+}
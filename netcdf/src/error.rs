@@ -34,11 +34,22 @@ pub enum Error {
     },
     /// Some index is greater than expected
     IndexMismatch,
+    /// Requested hyperslab does not fit in a dimension
+    OutOfBounds {
+        /// Position of the dimension in the variable
+        dim: usize,
+        /// Requested start along the dimension
+        start: usize,
+        /// Requested number of elements along the dimension
+        count: usize,
+        /// Length of the dimension
+        len: usize,
+    },
     /// Requested a mismatched total slice
     SliceMismatch,
     /// Requested a zero slice
     ZeroSlice,
-    /// Zero stride or matched with length != 1
+    /// Zero or negative stride
     Stride,
     /// Supplied the wrong type of parameter
     TypeMismatch,
@@ -46,6 +57,14 @@ pub enum Error {
     TypeUnknown(nc_type),
     /// Variable/dimension already exists
     AlreadyExists,
+    /// A dimension, variable, group or attribute with
+    /// this name already exists (`NC_ENAMEINUSE`)
+    NameInUse {
+        /// The kind of object which was added or renamed, e.g. `"dimension"`
+        kind: &'static str,
+        /// Name of the object
+        name: String,
+    },
     /// Could not find variable/attribute/etc
     NotFound(String),
     /// Slice lengths are ambiguous
@@ -60,6 +79,14 @@ pub enum Error {
     Utf8Conversion(std::string::FromUtf8Error),
     /// String contains NULL characters
     NulError(std::ffi::NulError),
+    /// Path can not be passed to `netCDF`, it contains
+    /// NULL characters or is not valid utf-8
+    InvalidPath(std::path::PathBuf),
+    /// Definitions can not be changed after data has been
+    /// written to the variable (`NC_ELATEDEF`)
+    TooLate,
+    /// The operation is not supported by `netCDF`
+    Unsupported(&'static str),
 }
 
 impl Error {
@@ -68,6 +95,46 @@ impl Error {
     pub fn is_ambigous(&self) -> bool {
         matches!(self, Self::Ambiguous)
     }
+
+    /// Status code from `netCDF`, if the error came from the library
+    /// and was not mapped to [`AlreadyExists`](Self::AlreadyExists)
+    pub fn code(&self) -> Option<nc_type> {
+        match self {
+            Self::Netcdf(x) => Some(*x),
+            Self::TooLate => Some(netcdf_sys::NC_ELATEDEF),
+            Self::NameInUse { .. } => Some(netcdf_sys::NC_ENAMEINUSE),
+            _ => None,
+        }
+    }
+
+    /// Was the error due to a missing variable,
+    /// attribute, dimension or group?
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+            || matches!(
+                self.code(),
+                Some(
+                    netcdf_sys::NC_ENOTVAR
+                        | netcdf_sys::NC_ENOTATT
+                        | netcdf_sys::NC_EBADDIM
+                        | netcdf_sys::NC_ENOGRP
+                )
+            )
+    }
+
+    /// Was the error due to the type of a value or variable?
+    pub fn is_type_mismatch(&self) -> bool {
+        matches!(self, Self::TypeMismatch)
+            || matches!(
+                self.code(),
+                Some(netcdf_sys::NC_EBADTYPE | netcdf_sys::NC_ECHAR)
+            )
+    }
+
+    /// Was the error due to writing to a file opened read-only?
+    pub fn is_permission_denied(&self) -> bool {
+        self.code() == Some(netcdf_sys::NC_EPERM)
+    }
 }
 
 impl std::error::Error for Error {
@@ -95,6 +162,8 @@ impl From<nc_type> for Error {
             || nc == netcdf_sys::NC_ENAMEINUSE
         {
             Self::AlreadyExists
+        } else if nc == netcdf_sys::NC_ELATEDEF {
+            Self::TooLate
         } else {
             Self::Netcdf(nc)
         }
@@ -133,13 +202,22 @@ impl fmt::Display for Error {
             Self::IndexLen => write!(f, "indices does not match in length with the variable"),
             Self::SliceLen => write!(f, "slices does not match in length with the variable"),
             Self::IndexMismatch => write!(f, "requested index is bigger than the dimension length"),
+            Self::OutOfBounds {
+                dim,
+                start,
+                count,
+                len,
+            } => write!(
+                f,
+                "requested {count} elements from {start} along dimension {dim}, which has length {len}"
+            ),
             Self::SliceMismatch => write!(f, "requested slice is bigger than the dimension length"),
             Self::DimensionMismatch { wanted, actual } => write!(
                 f,
                 "requested dimension ({actual}) is bigger than the dimension length ({wanted})"
             ),
             Self::ZeroSlice => write!(f, "must request a slice length larger than zero"),
-            Self::Stride => write!(f, "invalid strides"),
+            Self::Stride => write!(f, "invalid strides, all strides must be positive"),
             Self::BufferLen { wanted, actual } => write!(
                 f,
                 "buffer size mismatch, has size {actual}, but needs size {wanted}",
@@ -147,6 +225,7 @@ impl fmt::Display for Error {
             Self::TypeMismatch => write!(f, "netcdf types does not correspond to what is defined"),
             Self::TypeUnknown(t) => write!(f, "netcdf type {t} is not known"),
             Self::AlreadyExists => write!(f, "variable/group/dimension already exists"),
+            Self::NameInUse { kind, name } => write!(f, "{kind} {name} already exists"),
             Self::NotFound(x) => write!(f, "could not find {x}"),
             Self::Netcdf(x) => {
                 let msg;
@@ -164,6 +243,9 @@ impl fmt::Display for Error {
             Self::WrongDataset => write!(f, "this identifier does not belong in this dataset"),
             Self::Utf8Conversion(e) => e.fmt(f),
             Self::NulError(e) => write!(f, "string value contains null bytes {e}"),
+            Self::InvalidPath(p) => write!(f, "invalid path {}", p.display()),
+            Self::TooLate => write!(f, "can not change definitions after writing data"),
+            Self::Unsupported(x) => x.fmt(f),
         }
     }
 }
@@ -177,3 +259,15 @@ pub(crate) fn checked(err: nc_type) -> Result<()> {
     }
     Ok(())
 }
+
+/// Like [`checked`], naming the object added as `kind` if
+/// the name is already in use
+pub(crate) fn checked_name(err: nc_type, kind: &'static str, name: &str) -> Result<()> {
+    if err == netcdf_sys::NC_ENAMEINUSE {
+        return Err(Error::NameInUse {
+            kind,
+            name: name.to_owned(),
+        });
+    }
+    checked(err)
+}
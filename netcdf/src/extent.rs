@@ -480,7 +480,7 @@ impl Extents {
                         actual: extents.len(),
                     });
                 }
-                let (start, count) = dims
+                let (start, count): (Vec<usize>, Vec<usize>) = dims
                     .iter()
                     .zip(extents)
                     .map(|(d, &e)| match e {
@@ -501,7 +501,11 @@ impl Extents {
                             ),
                     })
                     .unzip();
-                let stride = extents.iter().map(|e| e.stride().unwrap_or(1)).collect();
+                let stride: Vec<isize> = extents.iter().map(|e| e.stride().unwrap_or(1)).collect();
+                if stride.iter().any(|&s| s < 1) {
+                    return Err(error::Error::Stride);
+                }
+                check_bounds(dims, &start, &count, &stride)?;
                 (start, count, stride)
             }
         };
@@ -509,6 +513,42 @@ impl Extents {
     }
 }
 
+/// Checks that the hyperslab lies within the fixed size dimensions.
+/// Unlimited dimensions are skipped, these may grow when writing
+fn check_bounds(
+    dims: &[Dimension],
+    start: &[usize],
+    count: &[usize],
+    stride: &[isize],
+) -> Result<(), error::Error> {
+    for (dim, (((d, &start), &count), &stride)) in
+        dims.iter().zip(start).zip(count).zip(stride).enumerate()
+    {
+        if d.is_unlimited() {
+            continue;
+        }
+        let len = d.len();
+        // Position of the last element, stride is positive
+        let in_bounds = if count == 0 {
+            start <= len
+        } else {
+            (count - 1)
+                .checked_mul(stride.unsigned_abs())
+                .and_then(|offset| start.checked_add(offset))
+                .is_some_and(|last| last < len)
+        };
+        if !in_bounds {
+            return Err(error::Error::OutOfBounds {
+                dim,
+                start,
+                count,
+                len,
+            });
+        }
+    }
+    Ok(())
+}
+
 #[cfg(feature = "ndarray")]
 mod ndarray_impl {
     use super::*;
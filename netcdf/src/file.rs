@@ -5,7 +5,7 @@ use super::attribute::{AttrValue, Attribute};
 use super::dimension::{self, Dimension};
 use super::error;
 use super::group::{Group, GroupMut};
-use super::variable::{NcPutGet, Variable, VariableMut};
+use super::variable::{NcPutGet, Variable, VariableBuilder, VariableMut};
 use netcdf_sys::*;
 use std::marker::PhantomData;
 use std::path;
@@ -19,21 +19,24 @@ impl Drop for RawFile {
     fn drop(&mut self) {
         unsafe {
             // Can't really do much with an error here
-            let _err = error::checked(super::with_lock(|| nc_close(self.ncid)));
+            let _err = error::checked(super::with_close_lock(self.ncid, || nc_close(self.ncid)));
         }
     }
 }
 
+/// Converts a path to a null terminated string for the `netCDF` library,
+/// using the raw bytes of the path on unix
 #[cfg(unix)]
-fn get_ffi_from_path(path: &path::Path) -> Vec<u8> {
+fn get_ffi_from_path(path: &path::Path) -> error::Result<std::ffi::CString> {
     use std::os::unix::ffi::OsStrExt;
-    let mut bytes = path.as_os_str().as_bytes().to_vec();
-    bytes.push(0);
-    bytes
+    std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| error::Error::InvalidPath(path.to_path_buf()))
 }
 #[cfg(not(unix))]
-fn get_ffi_from_path(path: &path::Path) -> std::ffi::CString {
-    std::ffi::CString::new(path.to_str().unwrap()).unwrap()
+fn get_ffi_from_path(path: &path::Path) -> error::Result<std::ffi::CString> {
+    path.to_str()
+        .and_then(|p| std::ffi::CString::new(p).ok())
+        .ok_or_else(|| error::Error::InvalidPath(path.to_path_buf()))
 }
 
 bitflags::bitflags! {
@@ -61,10 +64,148 @@ bitflags::bitflags! {
     }
 }
 
+/// Builder for opening and creating files with the
+/// `NC_SHARE`, `NC_DISKLESS` and `NC_WRITE` flags
+///
+/// [`open`](Self::open) gives a read only [`File`], which is opened
+/// by `netCDF` in write mode if [`write`](Self::write) is set, while
+/// [`append`](Self::append) and [`create`](Self::create) always
+/// give a writable [`MutableFile`]
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // Reader of a classic file which is written to by another process
+/// let file = netcdf::OpenOptions::new().share(true).open("shared.nc")?;
+/// // Scratch file which is never written to disk
+/// let mut scratch = netcdf::OpenOptions::new()
+///     .diskless(true)
+///     .create("scratch.nc")?;
+/// // In memory copy of a file, opened with `NC_DISKLESS | NC_WRITE`
+/// let copy = netcdf::OpenOptions::new()
+///     .diskless(true)
+///     .write(true)
+///     .open("data.nc")?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpenOptions {
+    options: Options,
+}
+
+impl OpenOptions {
+    /// Options for opening `netCDF-4` files, without any flags set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the internal buffering (`NC_SHARE`), making changes
+    /// visible to other processes sooner. Only meaningful for
+    /// classic files, `netCDF-4` files ignore this flag
+    pub fn share(&mut self, share: bool) -> &mut Self {
+        self.options.set(Options::SHARE, share);
+        self
+    }
+
+    /// Keep the file in memory (`NC_DISKLESS`). Opened files are
+    /// read into memory, and created files are not written to disk.
+    /// Works with both classic and `netCDF-4` files
+    pub fn diskless(&mut self, diskless: bool) -> &mut Self {
+        self.options.set(Options::DISKLESS, diskless);
+        self
+    }
+
+    /// Open the file in write mode (`NC_WRITE`) with [`open`](Self::open),
+    /// [`append`](Self::append) always opens in write mode. Works with
+    /// both classic and `netCDF-4` files
+    pub fn write(&mut self, write: bool) -> &mut Self {
+        self.options.set(Options::WRITE, write);
+        self
+    }
+
+    /// Format of files created with [`create`](Self::create),
+    /// the default is [`Format::Netcdf4`]
+    pub fn format(&mut self, format: Format) -> &mut Self {
+        self.options
+            .remove(Options::all() & !(Options::SHARE | Options::DISKLESS | Options::WRITE));
+        self.options |= format.into();
+        self
+    }
+
+    /// Open a file, in read only mode unless [`write`](Self::write) is set
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn open<P: AsRef<path::Path>>(&self, path: P) -> error::Result<File> {
+        RawFile::open_with(path.as_ref(), self.open_options())
+    }
+
+    /// Open a file in append mode (read/write)
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn append<P: AsRef<path::Path>>(&self, path: P) -> error::Result<MutableFile> {
+        RawFile::append_with(path.as_ref(), self.open_options())
+    }
+
+    /// Create a new file, overwriting any existing file
+    ///
+    /// # Errors
+    ///
+    /// netcdf error
+    pub fn create<P: AsRef<path::Path>>(&self, path: P) -> error::Result<MutableFile> {
+        let mut options = self.options;
+        if !options.intersects(Options::CLASSIC | Options::_64BIT_OFFSET | Options::_64BIT_DATA) {
+            options |= Options::NETCDF4;
+        }
+        RawFile::create_with(path.as_ref(), options)
+    }
+
+    /// The format is detected when opening a file
+    fn open_options(&self) -> Options {
+        self.options & (Options::SHARE | Options::DISKLESS | Options::WRITE)
+    }
+}
+
+/// On-disk format of a `netCDF` file
+///
+/// Converts into [`Options`] for use with `create_with`
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let file = netcdf::create_with("classic.nc", netcdf::Format::Classic.into())?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The original `netCDF` format (`CDF-1`)
+    Classic,
+    /// Classic format with 64 bit file offsets (`CDF-2`)
+    Offset64,
+    /// Classic format with 64 bit dimensions and sizes (`CDF-5`)
+    Cdf5,
+    /// `hdf5` based storage, supports groups and user defined types
+    Netcdf4,
+    /// `hdf5` based storage, restricted to the classic data model
+    Netcdf4Classic,
+}
+
+impl From<Format> for Options {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Classic => Self::CLASSIC,
+            Format::Offset64 => Self::_64BIT_OFFSET,
+            Format::Cdf5 => Self::_64BIT_DATA,
+            Format::Netcdf4 => Self::NETCDF4,
+            Format::Netcdf4Classic => Self::NETCDF4 | Self::CLASSIC,
+        }
+    }
+}
+
 impl RawFile {
     /// Open a `netCDF` file in read only mode.
     pub(crate) fn open_with(path: &path::Path, options: Options) -> error::Result<File> {
-        let f = get_ffi_from_path(path);
+        let f = get_ffi_from_path(path)?;
         let mut ncid: nc_type = 0;
         unsafe {
             error::checked(super::with_lock(|| {
@@ -74,15 +215,33 @@ impl RawFile {
         Ok(File(Self { ncid }))
     }
 
+    /// Open a remote dataset, the url is passed unmodified to `netCDF`
+    pub(crate) fn open_dap(url: &str, options: Options) -> error::Result<File> {
+        if cfg!(not(feature = "has-dap")) {
+            return Err(format!("can not open {url}, netCDF was built without DAP support").into());
+        }
+        let f = std::ffi::CString::new(url)?;
+        let mut ncid: nc_type = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_open(f.as_ptr(), options.bits(), &mut ncid)
+            }))?;
+        }
+        Ok(File(Self { ncid }))
+    }
+
     /// Open a `netCDF` file in append mode (read/write).
     pub(crate) fn append_with(path: &path::Path, options: Options) -> error::Result<MutableFile> {
         let file = Self::open_with(path, options | Options::WRITE)?;
-        Ok(MutableFile(file))
+        Ok(MutableFile {
+            file,
+            default_compression: None,
+        })
     }
 
     /// Create a new `netCDF` file
     pub(crate) fn create_with(path: &path::Path, options: Options) -> error::Result<MutableFile> {
-        let f = get_ffi_from_path(path);
+        let f = get_ffi_from_path(path)?;
         let mut ncid: nc_type = -1;
         unsafe {
             error::checked(super::with_lock(|| {
@@ -90,7 +249,10 @@ impl RawFile {
             }))?;
         }
 
-        Ok(MutableFile(File(Self { ncid })))
+        Ok(MutableFile {
+            file: File(Self { ncid }),
+            default_compression: None,
+        })
     }
 
     #[cfg(feature = "has-mmap")]
@@ -98,7 +260,7 @@ impl RawFile {
         name: Option<&str>,
         mem: &'buffer [u8],
     ) -> error::Result<MemFile<'buffer>> {
-        let cstr = std::ffi::CString::new(name.unwrap_or("/")).unwrap();
+        let cstr = std::ffi::CString::new(name.unwrap_or("/"))?;
         let mut ncid = 0;
         unsafe {
             error::checked(super::with_lock(|| {
@@ -106,7 +268,7 @@ impl RawFile {
                     cstr.as_ptr(),
                     NC_NOWRITE,
                     mem.len(),
-                    mem.as_ptr() as *const u8 as *mut _,
+                    mem.as_ptr() as *mut _,
                     &mut ncid,
                 )
             }))?;
@@ -114,6 +276,26 @@ impl RawFile {
 
         Ok(MemFile(File(Self { ncid }), PhantomData))
     }
+
+    #[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+    pub(crate) fn create_in_memory(
+        name: Option<&str>,
+        initial_size: usize,
+        options: Options,
+    ) -> error::Result<MemFileMut> {
+        let cstr = std::ffi::CString::new(name.unwrap_or("/"))?;
+        let mut ncid = -1;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_create_mem(cstr.as_ptr(), options.bits(), initial_size, &mut ncid)
+            }))?;
+        }
+
+        Ok(MemFileMut(MutableFile {
+            file: File(Self { ncid }),
+            default_compression: None,
+        }))
+    }
 }
 
 #[derive(Debug)]
@@ -124,10 +306,13 @@ pub struct File(RawFile);
 impl File {
     /// path used to open/create the file
     ///
-    /// #Errors
+    /// On unix the path is built from the raw bytes returned
+    /// by `netCDF`, and need not be valid UTF-8
+    ///
+    /// # Errors
     ///
     /// Netcdf layer could fail, or the resulting path
-    /// could contain an invalid UTF8 sequence
+    /// could contain an invalid UTF8 sequence on non-unix platforms
     pub fn path(&self) -> error::Result<std::path::PathBuf> {
         let name: Vec<u8> = {
             let mut pathlen = 0;
@@ -158,14 +343,67 @@ impl File {
         }
     }
 
-    /// Main entrypoint for interacting with the netcdf file.
-    pub fn root(&self) -> Option<Group> {
+    /// Format of the file on disk
+    ///
+    /// Features such as groups and user defined types
+    /// are only available for [`Format::Netcdf4`]
+    pub fn format(&self) -> error::Result<Format> {
         let mut format = 0;
-        unsafe { error::checked(super::with_lock(|| nc_inq_format(self.ncid(), &mut format))) }
-            .unwrap();
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_format(self.ncid(), &mut format)))?;
+        }
 
         match format {
-            NC_FORMAT_NETCDF4 | NC_FORMAT_NETCDF4_CLASSIC => Some(Group {
+            NC_FORMAT_CLASSIC => Ok(Format::Classic),
+            NC_FORMAT_64BIT_OFFSET => Ok(Format::Offset64),
+            NC_FORMAT_CDF5 => Ok(Format::Cdf5),
+            NC_FORMAT_NETCDF4 => Ok(Format::Netcdf4),
+            NC_FORMAT_NETCDF4_CLASSIC => Ok(Format::Netcdf4Classic),
+            _ => Err(format!("unknown file format {format}").into()),
+        }
+    }
+
+    /// Let reads of variable data from this file (`values`, `value`,
+    /// `values_to`, ...) run in parallel with reads from other files
+    /// which allow this
+    ///
+    /// Reads from the same file are still serialized by a lock owned by
+    /// the file, and all other calls into `netCDF` (opening, closing,
+    /// defining, writing, and reading metadata) wait for running reads
+    /// and have exclusive use of the library
+    ///
+    /// # Safety
+    ///
+    /// `netCDF-c` does not promise that reads from different files
+    /// are safe to run in parallel. The caller must ensure that the
+    /// linked `netCDF-c` and `hdf5` libraries support this, as the
+    /// check that `hdf5` is threadsafe does not cover `netCDF-c`
+    ///
+    /// # Errors
+    ///
+    /// The file is not a `netCDF-4` file, or `hdf5` is not threadsafe
+    /// (both [`Unsupported`](error::Error::Unsupported))
+    #[cfg(feature = "thread-safe-hdf5")]
+    pub unsafe fn enable_concurrent_reads(&self) -> error::Result<()> {
+        if !matches!(self.format()?, Format::Netcdf4 | Format::Netcdf4Classic) {
+            return Err(error::Error::Unsupported(
+                "concurrent reads require a netCDF-4 file",
+            ));
+        }
+        if !*super::HDF5_THREADSAFE {
+            return Err(error::Error::Unsupported(
+                "concurrent reads require a threadsafe hdf5",
+            ));
+        }
+        let mut files = super::LOCK.write().unwrap();
+        files.entry(self.ncid()).or_default();
+        Ok(())
+    }
+
+    /// Main entrypoint for interacting with the netcdf file.
+    pub fn root(&self) -> Option<Group> {
+        match self.format().unwrap() {
+            Format::Netcdf4 | Format::Netcdf4Classic => Some(Group {
                 ncid: self.ncid(),
                 _file: PhantomData,
             }),
@@ -188,6 +426,60 @@ impl File {
             .map(Result::unwrap)
     }
 
+    /// Iterator over all variables in the file, including all subgroups
+    ///
+    /// Each variable is paired with the path of its group, such as
+    /// `/` for the root group or `/model/ocean`. Groups are
+    /// visited depth first
+    pub fn variables_recursive(
+        &self,
+    ) -> impl Iterator<Item = error::Result<(String, Variable<'_>)>> {
+        super::group::variables_recursive_at(self.ncid())
+    }
+
+    /// Read all data in the file to check that it is readable,
+    /// see [`verify_with`](Self::verify_with)
+    ///
+    /// # Errors
+    ///
+    /// The first error when reading a variable
+    pub fn verify(&self) -> error::Result<()> {
+        self.verify_with(VERIFY_BLOCK_BYTES)
+    }
+
+    /// Read all data of all variables in the file, including subgroups,
+    /// to check that the file is not corrupt. The values are discarded
+    ///
+    /// Variables are read in blocks of at most about `block_bytes` bytes,
+    /// which bounds the memory used. Variables with the fletcher32 filter
+    /// enabled are checked against their checksums when read. Variables
+    /// of types with nested variable length members, such as compound
+    /// types containing strings, can not be freed after reading and are
+    /// skipped
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("upload.nc")?;
+    /// // Use at most 16 MiB for reading
+    /// if let Err(e) = file.verify_with(16 << 20) {
+    ///     eprintln!("rejecting upload.nc: {e}");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The first error when reading a variable, e.g. a checksum failure
+    pub fn verify_with(&self, block_bytes: usize) -> error::Result<()> {
+        for var in self.variables_recursive() {
+            let (_path, var) = var?;
+            var.read_blocks(block_bytes)?;
+        }
+        Ok(())
+    }
+
     /// Get a single attribute
     pub fn attribute<'f>(&'f self, name: &str) -> Option<Attribute<'f>> {
         Attribute::find_from_name(self.ncid(), None, name).unwrap()
@@ -198,11 +490,69 @@ impl File {
             .unwrap()
             .map(Result::unwrap)
     }
+    /// Number of variables in the root group
+    ///
+    /// This is cheaper than counting the variables from an iterator
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn num_variables(&self) -> error::Result<usize> {
+        let mut nvars = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_nvars(self.ncid(), &mut nvars)))?;
+        }
+        Ok(nvars.try_into()?)
+    }
+    /// Number of dimensions defined in the root group
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn num_dimensions(&self) -> error::Result<usize> {
+        let mut ndims = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_ndims(self.ncid(), &mut ndims)))?;
+        }
+        Ok(ndims.try_into()?)
+    }
+    /// Number of attributes of the root group
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn num_attributes(&self) -> error::Result<usize> {
+        let mut natts = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_natts(self.ncid(), &mut natts)))?;
+        }
+        Ok(natts.try_into()?)
+    }
+    /// Read the names and values of all attributes in the root group
+    ///
+    /// Useful for dumping the global metadata of a file, the values
+    /// are read eagerly into owned [`AttrValue`]s
+    ///
+    /// # Errors
+    ///
+    /// Not a netCDF file, or an attribute with a type which can not be read
+    pub fn attribute_map(&self) -> error::Result<Vec<(String, AttrValue)>> {
+        crate::attribute::attribute_map(self.0.ncid, None)
+    }
 
     /// Get a single dimension
     pub fn dimension<'f>(&self, name: &str) -> Option<Dimension<'f>> {
         super::dimension::dimension_from_name(self.ncid(), name).unwrap()
     }
+    /// Get the dimension of an [`Identifier`](dimension::Identifier),
+    /// which may be defined in any group of the file
+    ///
+    /// Returns `None` if the identifier is not a dimension of this file.
+    /// Unlike names, identifiers distinguish dimensions with the same
+    /// name in different groups
+    pub fn dimension_by_id(&self, id: dimension::Identifier) -> Option<Dimension<'_>> {
+        super::dimension::from_identifier(self.ncid(), id).unwrap()
+    }
     /// Iterator over all dimensions in the root group
     pub fn dimensions(&self) -> impl Iterator<Item = Dimension> {
         super::dimension::dimensions_from_location(self.ncid())
@@ -210,6 +560,11 @@ impl File {
             .map(Result::unwrap)
     }
 
+    /// Iterator over all unlimited dimensions in the root group
+    pub fn unlimited_dimensions(&self) -> impl Iterator<Item = Dimension<'_>> {
+        self.dimensions().filter(Dimension::is_unlimited)
+    }
+
     /// Get a group
     ///
     /// # Errors
@@ -218,6 +573,17 @@ impl File {
     pub fn group<'f>(&'f self, name: &str) -> error::Result<Option<Group<'f>>> {
         super::group::group_from_name(self.ncid(), name)
     }
+    /// Get a nested group from a path such as `/model/ocean/grid`,
+    /// the leading `/` is optional
+    ///
+    /// Returns `None` if any group along the path does not exist
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    pub fn group_path<'f>(&'f self, path: &str) -> error::Result<Option<Group<'f>>> {
+        super::group::group_from_path(self.ncid(), path)
+    }
     /// Iterator over all subgroups in the root group
     ///
     /// # Errors
@@ -226,21 +592,170 @@ impl File {
     pub fn groups(&self) -> error::Result<impl Iterator<Item = Group>> {
         super::group::groups_at_ncid(self.ncid())
     }
+    /// Variables of the root group in the order they were defined
+    ///
+    /// `netCDF` numbers the variables of a group in the order of their
+    /// definition, and this sorts the variables by these ids, to give
+    /// a reproducible order regardless of the iteration order of
+    /// [`variables`](Self::variables). For `HDF5` files which were not
+    /// written by `netCDF` and do not track the creation order, the ids
+    /// follow the order of the names instead
+    ///
+    /// Attributes are also numbered in the order of definition, and
+    /// [`attributes`](Self::attributes) yields them in this order.
+    /// Deleting an attribute renumbers the attributes after it
+    ///
+    /// # Errors
+    ///
+    /// The first error while reading the variables
+    pub fn variables_in_creation_order(&self) -> error::Result<Vec<Variable<'_>>> {
+        let mut variables = self.collect_variables()?;
+        variables.sort_by_key(|var| var.varid);
+        Ok(variables)
+    }
+    /// Collect all variables in the root group, as [`variables`](Self::variables)
+    ///
+    /// The variables still borrow the file, but the list can be stored,
+    /// sorted and indexed, unlike the iterator
+    ///
+    /// # Errors
+    ///
+    /// The first error while reading the variables
+    pub fn collect_variables(&self) -> error::Result<Vec<Variable<'_>>> {
+        super::variable::variables_at_ncid(self.ncid())?.collect()
+    }
+    /// Collect all subgroups of the root group, as [`groups`](Self::groups)
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file, or the first error while reading the groups
+    pub fn collect_groups(&self) -> error::Result<Vec<Group<'_>>> {
+        Ok(super::group::groups_at_ncid(self.ncid())?.collect())
+    }
     /// Return all types in the root group
     pub fn types(&self) -> error::Result<impl Iterator<Item = super::types::VariableType>> {
         super::types::all_at_location(self.ncid()).map(|x| x.map(Result::unwrap))
     }
 }
 
+/// Read only file which can be cloned and shared between threads
+///
+/// All clones refer to the same open file, which is closed when the
+/// last clone is dropped. Calls into `netCDF` are serialized by a
+/// global lock, so reads from several threads do not run in parallel,
+/// but each thread avoids opening the file again. See the
+/// `thread-safe-hdf5` feature for reading variable data from several
+/// files in parallel
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let file = netcdf::open("data.nc")?.into_shared();
+/// let workers = (0..4)
+///     .map(|_| {
+///         let file = file.clone();
+///         std::thread::spawn(move || file.variable("v").unwrap().len())
+///     })
+///     .collect::<Vec<_>>();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedFile(std::sync::Arc<File>);
+
+impl std::ops::Deref for SharedFile {
+    type Target = File;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl SharedFile {
+    pub(crate) fn ncid(&self) -> nc_type {
+        self.0.ncid()
+    }
+}
+
+impl From<File> for SharedFile {
+    fn from(file: File) -> Self {
+        Self(std::sync::Arc::new(file))
+    }
+}
+
+impl File {
+    /// Convert into a file which can be cloned and shared between threads
+    pub fn into_shared(self) -> SharedFile {
+        self.into()
+    }
+
+    /// Convert into a writable file, for inspecting a file
+    /// before deciding to modify it
+    ///
+    /// A file which was opened for writing is converted as is. Otherwise
+    /// the file is closed and reopened from the same path in append mode,
+    /// keeping the [`SHARE`](Options::SHARE) and
+    /// [`DISKLESS`](Options::DISKLESS) options. Files kept in memory
+    /// (`MemFile`) can not be converted
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?;
+    /// if file.attribute("history").is_none() {
+    ///     let mut file = file.into_mut()?;
+    ///     file.add_attribute("history", "checked")?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The file could not be reopened for writing, e.g. a remote dataset or
+    /// missing permissions. The read only file is closed in this case
+    pub fn into_mut(self) -> error::Result<MutableFile> {
+        let mut format = 0;
+        let mut mode = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_format_extended(self.ncid(), &mut format, &mut mode)
+            }))?;
+        }
+        if mode & NC_WRITE != 0 {
+            return Ok(MutableFile {
+                file: self,
+                default_compression: None,
+            });
+        }
+        let path = self.path()?;
+        let options = Options::from_bits_truncate(mode) & (Options::SHARE | Options::DISKLESS);
+        drop(self);
+        RawFile::append_with(&path, options)
+    }
+}
+
+/// Default size of the blocks read by [`File::verify`]
+const VERIFY_BLOCK_BYTES: usize = 1 << 24;
+
+/// Number of elements copied at once by [`MutableFile::copy_variable_from`]
+const COPY_BLOCK_ELEMENTS: usize = 1 << 20;
+
 /// Mutable access to file
 #[derive(Debug)]
 #[allow(clippy::module_name_repetitions)]
-pub struct MutableFile(File);
+pub struct MutableFile {
+    file: File,
+    /// Deflate level and shuffle of new variables,
+    /// see [`MutableFile::set_default_compression`]
+    default_compression: Option<(u8, bool)>,
+}
 
 impl std::ops::Deref for MutableFile {
     type Target = File;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.file
     }
 }
 
@@ -249,7 +764,9 @@ impl MutableFile {
     ///
     /// Return None if this can't be a root group
     pub fn root_mut(&mut self) -> Option<GroupMut> {
-        self.root().map(|root| GroupMut(root, PhantomData))
+        let default_compression = self.default_compression;
+        self.root()
+            .map(|root| GroupMut::new(root, default_compression))
     }
 
     /// Get a mutable variable from the group
@@ -279,7 +796,7 @@ impl MutableFile {
     /// File does not support groups
     pub fn group_mut<'f>(&'f mut self, name: &str) -> error::Result<Option<GroupMut<'f>>> {
         self.group(name)
-            .map(|g| g.map(|g| GroupMut(g, PhantomData)))
+            .map(|g| g.map(|g| GroupMut::new(g, self.default_compression)))
     }
     /// Iterator over all groups (mutable access)
     ///
@@ -287,7 +804,9 @@ impl MutableFile {
     ///
     /// File does not support groups
     pub fn groups_mut(&mut self) -> error::Result<impl Iterator<Item = GroupMut>> {
-        self.groups().map(|g| g.map(|g| GroupMut(g, PhantomData)))
+        let default_compression = self.default_compression;
+        self.groups()
+            .map(|g| g.map(move |g| GroupMut::new(g, default_compression)))
     }
 
     /// Add an attribute to the root group
@@ -298,6 +817,37 @@ impl MutableFile {
         Attribute::put(self.ncid(), NC_GLOBAL, name, val.into())
     }
 
+    /// Add an attribute with the exact type of `val`, see [`add_attribute`](Self::add_attribute)
+    pub fn add_attribute_typed<'a>(
+        &'a mut self,
+        name: &str,
+        val: AttrValue,
+    ) -> error::Result<Attribute<'a>> {
+        self.add_attribute(name, val)
+    }
+
+    /// Delete an attribute from the root group
+    ///
+    /// # Errors
+    ///
+    /// `NC_ENOTATT` if the attribute does not exist
+    pub fn del_attribute(&mut self, name: &str) -> error::Result<()> {
+        Attribute::delete(self.ncid(), NC_GLOBAL, name)
+    }
+
+    /// Rename an attribute of the root group, keeping its value
+    ///
+    /// Files in the classic format which are not in define mode
+    /// will temporarily reenter define mode if this is required
+    ///
+    /// # Errors
+    ///
+    /// `NC_ENOTATT` if the attribute does not exist, or `new` is already
+    /// in use ([`NameInUse`](error::Error::NameInUse))
+    pub fn rename_attribute(&mut self, old: &str, new: &str) -> error::Result<()> {
+        Attribute::rename(self.ncid(), NC_GLOBAL, old, new)
+    }
+
     /// Adds a dimension with the given name and size. A size of zero gives an unlimited dimension
     pub fn add_dimension<'f>(&'f mut self, name: &str, len: usize) -> error::Result<Dimension<'f>> {
         super::dimension::add_dimension_at(self.ncid(), name, len)
@@ -307,9 +857,103 @@ impl MutableFile {
         self.add_dimension(name, 0)
     }
 
+    /// Get the dimension `name` in the root group, adding it if it
+    /// does not exist yet. A `len` of zero gives an unlimited dimension
+    ///
+    /// This allows initializing a file which may already contain
+    /// the dimensions, e.g. when appending to an existing file
+    ///
+    /// # Errors
+    ///
+    /// The dimension exists with another length, or is unlimited
+    /// when a fixed length was requested (or the reverse)
+    pub fn get_or_add_dimension<'f>(
+        &'f mut self,
+        name: &str,
+        len: usize,
+    ) -> error::Result<Dimension<'f>> {
+        let dim = match dimension::from_name(self.ncid(), name)? {
+            Some(dim) => dim,
+            None => return self.add_dimension(name, len),
+        };
+        let matches = if len == 0 {
+            dim.is_unlimited()
+        } else {
+            !dim.is_unlimited() && dim.len() == len
+        };
+        if !matches {
+            let describe = |unlimited, len| {
+                if unlimited {
+                    "unlimited".to_owned()
+                } else {
+                    format!("length {len}")
+                }
+            };
+            return Err(format!(
+                "dimension {name} exists as {}, not {}",
+                describe(dim.is_unlimited(), dim.len()),
+                describe(len == 0, len)
+            )
+            .into());
+        }
+        Ok(dim)
+    }
+
+    /// Remove a variable from the root group
+    ///
+    /// `netCDF` has no way of deleting a variable once it has been
+    /// defined, this always fails. To drop variables, copy the
+    /// variables which should be kept to a new file, e.g. using
+    /// [`MutableFile::copy_variable_from`](crate::MutableFile::copy_variable_from)
+    ///
+    /// # Errors
+    ///
+    /// [`NotFound`](error::Error::NotFound) if there is no such variable,
+    /// otherwise [`Unsupported`](error::Error::Unsupported)
+    pub fn remove_variable(&mut self, name: &str) -> error::Result<()> {
+        if Variable::find_from_name(self.ncid(), name)?.is_none() {
+            return Err(error::Error::NotFound(format!("variable {name}")));
+        }
+        Err(error::Error::Unsupported(
+            "netCDF does not support deleting variables, copy the remaining variables to a new file instead",
+        ))
+    }
+
+    /// Rename a dimension in the root group
+    ///
+    /// Rename coordinate variables and dimensions before writing
+    /// data, `netCDF-4` files may otherwise lose the written data
+    ///
+    /// # Errors
+    ///
+    /// No dimension named `old`, or `new` is already in use
+    /// ([`NameInUse`](error::Error::NameInUse))
+    pub fn rename_dimension(&mut self, old: &str, new: &str) -> error::Result<()> {
+        super::dimension::rename_dimension_at(self.ncid(), old, new)
+    }
+
     /// Add an empty group to the dataset
     pub fn add_group<'f>(&'f mut self, name: &str) -> error::Result<GroupMut<'f>> {
-        GroupMut::add_group_at(self.ncid(), name)
+        GroupMut::add_group_at(self.ncid(), name, self.default_compression)
+    }
+
+    /// Add nested groups from a path such as `/model/ocean/grid`,
+    /// creating intermediate groups as needed
+    ///
+    /// Returns the deepest group, which is reused if it already exists
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create("nested.nc")?;
+    /// let mut grid = file.add_group_path("/model/ocean/grid")?;
+    /// grid.add_dimension("x", 10)?;
+    /// assert!(file.group_path("model/ocean")?.is_some());
+    /// # Ok(()) }
+    /// ```
+    pub fn add_group_path<'f>(&'f mut self, path: &str) -> error::Result<GroupMut<'f>> {
+        GroupMut::add_group_path_at(self.ncid(), path, self.default_compression)
     }
 
     /// Create a Variable into the dataset, with no data written into it
@@ -324,7 +968,97 @@ impl MutableFile {
     where
         T: NcPutGet,
     {
-        VariableMut::add_from_str(self.ncid(), T::NCTYPE, name, dims)
+        let mut var = VariableMut::add_from_str(self.ncid(), T::NCTYPE, name, dims)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
+    }
+
+    /// Get the variable `name` in the root group, adding it
+    /// if it does not exist yet
+    ///
+    /// An existing variable must have the type `T` and the
+    /// dimensions `dims`
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) if the variable exists
+    /// with another type, the variable exists with other dimensions,
+    /// missing dimensions, or netcdf error
+    pub fn get_or_add_variable<'f, T>(
+        &'f mut self,
+        name: &str,
+        dims: &[&str],
+    ) -> error::Result<VariableMut<'f>>
+    where
+        T: NcPutGet,
+    {
+        let var = match Variable::find_from_name(self.ncid(), name)? {
+            Some(var) => var,
+            None => return self.add_variable::<T>(name, dims),
+        };
+        if var.vartype != T::NCTYPE {
+            return Err(error::Error::TypeMismatch);
+        }
+        let conflict =
+            || error::Error::from(format!("variable {name} exists with other dimensions"));
+        if var.dimensions().len() != dims.len() {
+            return Err(conflict());
+        }
+        for (dim, &dimname) in var.dimensions().iter().zip(dims) {
+            let dimid = dimension::from_name_toid(self.ncid(), dimname)?
+                .ok_or_else(|| error::Error::NotFound(format!("dimensions {dimname}")))?;
+            let id = dim.identifier();
+            if id.ncid != self.ncid() || id.dimid != dimid {
+                return Err(conflict());
+            }
+        }
+        Ok(VariableMut(var, PhantomData))
+    }
+
+    /// Configure a variable with a builder, which creates the
+    /// variable with its chunking, compression and fill value
+    /// in a single define-mode session
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create("builder.nc")?;
+    /// file.add_unlimited_dimension("time")?;
+    /// file.add_dimension("lat", 180)?;
+    /// file.add_dimension("lon", 360)?;
+    /// let var = file
+    ///     .variable_builder::<f32>("temperature")
+    ///     .dimensions(&["time", "lat", "lon"])
+    ///     .chunking(&[1, 180, 360])
+    ///     .compression(4, true)
+    ///     .fill_value(-9999.0)
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn variable_builder<'f, T>(&'f mut self, name: &str) -> VariableBuilder<'f, T>
+    where
+        T: NcPutGet,
+    {
+        VariableBuilder::new(self.ncid(), name).default_compression(self.default_compression)
+    }
+
+    /// Create a variable with the type given by its `netCDF` type id,
+    /// e.g. `NC_STRING`, or the id of a user defined type
+    ///
+    /// # Errors
+    ///
+    /// [`TypeUnknown`](error::Error::TypeUnknown) if there is no such
+    /// type, missing dimensions, or netcdf error
+    pub fn add_variable_with_type_id<'f>(
+        &'f mut self,
+        name: &str,
+        dims: &[&str],
+        xtype: nc_type,
+    ) -> error::Result<VariableMut<'f>> {
+        let mut var = VariableMut::add_from_type_id(self.ncid(), xtype, name, dims)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
     }
 
     /// Create a variable with the specified type
@@ -334,7 +1068,9 @@ impl MutableFile {
         dims: &[&str],
         typ: &super::types::VariableType,
     ) -> error::Result<VariableMut<'f>> {
-        VariableMut::add_from_str(self.ncid(), typ.id(), name, dims)
+        let mut var = VariableMut::add_from_str(self.ncid(), typ.id(), name, dims)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
     }
 
     /// Add an opaque datatype, with `size` bytes
@@ -387,7 +1123,390 @@ impl MutableFile {
     where
         T: NcPutGet,
     {
-        super::variable::add_variable_from_identifiers(self.ncid(), name, dims, T::NCTYPE)
+        let mut var =
+            super::variable::add_variable_from_identifiers(self.ncid(), name, dims, T::NCTYPE)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
+    }
+
+    /// Compress all variables created after this call, as if
+    /// [`VariableMut::set_compression`] was called on each of them
+    ///
+    /// `netCDF` has no default compression of its own, so this applies to
+    /// variables added to any group of this file, through the `add_variable`
+    /// methods, [`variable_builder`](Self::variable_builder) and
+    /// [`define`](Self::define), and the same methods of [`GroupMut`].
+    /// Variables created earlier, scalars, and strings or other types with
+    /// variable length members are not compressed.
+    ///
+    /// The deflate level of single variables can still be changed with
+    /// [`VariableMut::set_compression`] before writing data. Some versions
+    /// of `netCDF` keep the shuffle filter once it is enabled. Use
+    /// [`VariableBuilder::compression`] to replace the default entirely.
+    /// A `deflate_level` of 0 without `shuffle` turns the default off again
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create_with("compressed.nc", netcdf::Options::NETCDF4)?;
+    /// file.set_default_compression(4, true)?;
+    /// file.add_dimension("x", 1000)?;
+    /// file.add_variable::<f64>("a", &["x"])?;
+    /// file.add_variable::<f64>("b", &["x"])?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file, or `deflate_level` not valid
+    pub fn set_default_compression(
+        &mut self,
+        deflate_level: u8,
+        shuffle: bool,
+    ) -> error::Result<()> {
+        if deflate_level > 9 {
+            return Err(format!("invalid deflate level {deflate_level}, must be in 0..=9").into());
+        }
+        let mut format = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_format(self.ncid(), &mut format)))?;
+        }
+        if format != NC_FORMAT_NETCDF4 && format != NC_FORMAT_NETCDF4_CLASSIC {
+            return Err("compression requires a netCDF-4 file".into());
+        }
+        self.default_compression = if deflate_level == 0 && !shuffle {
+            None
+        } else {
+            Some((deflate_level, shuffle))
+        };
+        Ok(())
+    }
+
+    /// Set whether variables are prefilled with their fill value,
+    /// returning the previous mode (`true` for fill)
+    ///
+    /// This applies to data written after the call, and turning
+    /// fill off avoids writing every element twice when the
+    /// variables are overwritten anyway
+    ///
+    /// With fill turned off, reading elements which have not been
+    /// written gives whatever happens to be in the file instead of
+    /// the fill value, so every element should be written. Unwritten
+    /// strings and vlens which the library returns without data are
+    /// read as empty
+    ///
+    /// # Errors
+    ///
+    /// Not possible to change the fill mode of the file
+    pub fn set_fill_mode(&mut self, fill: bool) -> error::Result<bool> {
+        let mode = if fill { NC_FILL } else { NC_NOFILL };
+        let mut old = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_set_fill(self.ncid(), mode, &mut old)
+            }))?;
+        }
+        Ok(old == NC_FILL)
+    }
+
+    /// Enter define mode, allowing new dimensions, variables
+    /// and attributes to be added to classic files
+    ///
+    /// Newly created files start out in define mode, and `netCDF-4` files
+    /// switch between the modes automatically. Some methods, such as
+    /// [`rename_dimension`](Self::rename_dimension), temporarily reenter
+    /// define mode on their own, and return to data mode afterwards
+    ///
+    /// # Errors
+    ///
+    /// `NC_EINDEFINE` if the file is already in define mode
+    pub fn redef(&mut self) -> error::Result<()> {
+        unsafe { error::checked(super::with_lock(|| nc_redef(self.ncid()))) }
+    }
+
+    /// Leave define mode, allowing data to be written to classic files
+    ///
+    /// This is a no-op if the file is already in data mode
+    ///
+    /// # Errors
+    ///
+    /// The definitions are not valid, e.g. not enough space for the header
+    pub fn enddef(&mut self) -> error::Result<()> {
+        let e = unsafe { super::with_lock(|| nc_enddef(self.ncid())) };
+        if e == NC_ENOTINDEFINE {
+            return Ok(());
+        }
+        error::checked(e)
+    }
+
+    /// Define dimensions, variables and attributes in a single
+    /// define-mode session
+    ///
+    /// The file enters define mode once, runs `f`, and calls `nc_enddef`
+    /// when `f` returns, also if `f` returns an error. This avoids
+    /// repeated mode switches for classic files, which reorganise the
+    /// file on every `nc_enddef`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create_with("schema.nc", netcdf::Options::CLASSIC)?;
+    /// file.define(|def| {
+    ///     def.dimension("x", 10)?;
+    ///     def.unlimited_dimension("time")?;
+    ///     def.variable::<f64>("v", &["time", "x"])?;
+    ///     def.attribute("title", "batched")?;
+    ///     Ok(())
+    /// })?;
+    /// // The file is now in data mode
+    /// file.variable_mut("v").unwrap().put_values(&[0.0; 10], (0, ..))?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The error returned by `f`, or failure to enter or leave define mode
+    pub fn define<F, R>(&mut self, f: F) -> error::Result<R>
+    where
+        F: FnOnce(&mut Definer) -> error::Result<R>,
+    {
+        let ncid = self.ncid();
+        unsafe {
+            // New files are already in define mode
+            let e = super::with_lock(|| nc_redef(ncid));
+            if e != NC_EINDEFINE {
+                error::checked(e)?;
+            }
+        }
+        let ret = f(&mut Definer {
+            ncid,
+            compression: self.default_compression,
+            _file: PhantomData,
+        });
+        let e = self.enddef();
+        let ret = ret?;
+        e?;
+        Ok(ret)
+    }
+    /// Adds a variable using these dimensions, see [`GroupMut::add_variable_dims`]
+    ///
+    /// # Errors
+    ///
+    /// Dimensions belong to another file, or netcdf error
+    pub fn add_variable_dims<'f, T>(
+        &'f mut self,
+        name: &str,
+        dims: &[Dimension],
+    ) -> error::Result<VariableMut<'f>>
+    where
+        T: NcPutGet,
+    {
+        let ids = dims.iter().map(Dimension::identifier).collect::<Vec<_>>();
+        let mut var =
+            super::variable::add_variable_from_identifiers(self.ncid(), name, &ids, T::NCTYPE)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
+    }
+
+    /// Copy a variable, including its attributes and data, from another file
+    /// into the root group, optionally under a new name
+    ///
+    /// Missing dimensions are created in the root group, while existing
+    /// dimensions with the same name are reused if their lengths match.
+    /// Compression and chunking are preserved when both files are `netCDF-4`.
+    /// Data is copied in blocks along the first dimension. A classic target
+    /// file is left in data mode, also when the copy fails. Dimensions and
+    /// names are checked before anything is defined in the target
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let src = netcdf::open("input.nc")?;
+    /// let mut dst = netcdf::create("subset.nc")?;
+    /// dst.copy_variable_from(&src.variable("temperature").unwrap(), None)?;
+    /// dst.copy_variable_from(&src.variable("lat").unwrap(), Some("latitude"))?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A dimension exists with a different length, a variable with
+    /// the name exists ([`NameInUse`](error::Error::NameInUse)), the
+    /// variable has a user defined type, or netcdf error
+    pub fn copy_variable_from(
+        &mut self,
+        src: &Variable,
+        new_name: Option<&str>,
+    ) -> error::Result<()> {
+        let ncid = self.ncid();
+        let name = new_name.map_or_else(|| src.name(), str::to_owned);
+        let vartype = src.vartype();
+        if vartype.as_basic().is_none() && !vartype.is_string() {
+            return Err(format!("can not copy variable {name} of a user defined type").into());
+        }
+
+        if self.variable(&name).is_some() {
+            return Err(error::Error::NameInUse {
+                kind: "variable",
+                name,
+            });
+        }
+        // Check everything which can fail before defining anything,
+        // as variables can not be removed again
+        let mut dimnames = Vec::with_capacity(src.dimensions().len());
+        let mut new_dims = Vec::new();
+        for dim in src.dimensions() {
+            let dimname = dim.name();
+            match super::dimension::dimension_from_name(ncid, &dimname)? {
+                Some(existing) => {
+                    let compatible = if dim.is_unlimited() {
+                        existing.is_unlimited()
+                    } else {
+                        !existing.is_unlimited() && existing.len() == dim.len()
+                    };
+                    if !compatible {
+                        return Err(format!(
+                            "dimension {dimname} has length {} in the target, but {} in the source",
+                            existing.len(),
+                            dim.len()
+                        )
+                        .into());
+                    }
+                }
+                None if new_dims.iter().any(|(name, _)| name == &dimname) => {}
+                None => {
+                    let len = if dim.is_unlimited() { 0 } else { dim.len() };
+                    new_dims.push((dimname.clone(), len));
+                }
+            }
+            dimnames.push(dimname);
+        }
+        let (chunking, compression) = if src.is_netcdf4()?
+            && matches!(self.format()?, Format::Netcdf4 | Format::Netcdf4Classic)
+        {
+            (src.chunking()?, src.compression()?)
+        } else {
+            (None, None)
+        };
+        let attributes = src
+            .attributes()
+            .map(|attr| Ok((attr.name().to_owned(), attr.value()?)))
+            .collect::<error::Result<Vec<_>>>()?;
+
+        unsafe {
+            // New files are already in define mode
+            let e = super::with_lock(|| nc_redef(ncid));
+            if e != NC_EINDEFINE {
+                error::checked(e)?;
+            }
+        }
+        let define = || -> error::Result<VariableMut> {
+            for (dimname, len) in &new_dims {
+                super::dimension::add_dimension_at(ncid, dimname, *len)?;
+            }
+            let dimnames = dimnames.iter().map(String::as_str).collect::<Vec<_>>();
+            let mut dst = VariableMut::add_from_str(ncid, vartype.id(), &name, &dimnames)?;
+            if let Some(chunks) = chunking {
+                dst.set_chunking(&chunks)?;
+            }
+            if let Some((level, shuffle)) = compression {
+                dst.set_compression(level, shuffle)?;
+            }
+            for (attrname, value) in attributes {
+                dst.add_attribute(&attrname, value)?;
+            }
+            Ok(dst)
+        };
+        let dst = define();
+        // Leave define mode also on errors
+        let e = self.enddef();
+        let mut dst = dst?;
+        e?;
+
+        // Copy blocks of whole rows along the first dimension
+        let lens = src
+            .dimensions()
+            .iter()
+            .map(Dimension::len)
+            .collect::<Vec<_>>();
+        let (rows, row_len) = match lens.split_first() {
+            Some((&rows, rest)) => (rows, rest.iter().product::<usize>()),
+            None => (1, 1),
+        };
+        let rows_per_block = (COPY_BLOCK_ELEMENTS / row_len.max(1)).max(1);
+        for row in (0..rows).step_by(rows_per_block) {
+            let nrows = rows_per_block.min(rows - row);
+            let extents: super::extent::Extents = lens
+                .iter()
+                .enumerate()
+                .map(|(i, &len)| super::extent::Extent::SliceCount {
+                    start: if i == 0 { row } else { 0 },
+                    count: if i == 0 { nrows } else { len },
+                    stride: 1,
+                })
+                .collect::<Vec<_>>()
+                .into();
+            if vartype.is_string() {
+                let values = src.string_values(extents.clone())?;
+                dst.put_strings(&values, extents)?;
+            } else {
+                let mut buf = vec![0_u8; nrows * row_len * vartype.size()];
+                src.raw_values(&mut buf, extents.clone())?;
+                // Basic numeric types, any bytes are valid
+                unsafe { dst.put_raw_values(&buf, extents)? };
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush buffered data to disk without closing the file
+    ///
+    /// Makes the written data visible to other readers of the file,
+    /// and guards against data loss on a crash in long-running writers
+    pub fn sync(&mut self) -> error::Result<()> {
+        unsafe { error::checked(super::with_lock(|| nc_sync(self.ncid()))) }
+    }
+}
+
+/// Handle for adding items to the root group inside [`MutableFile::define`]
+#[derive(Debug)]
+pub struct Definer<'f> {
+    ncid: nc_type,
+    compression: Option<(u8, bool)>,
+    _file: PhantomData<&'f mut MutableFile>,
+}
+
+impl<'f> Definer<'f> {
+    /// Add a dimension, a size of zero gives an unlimited dimension
+    pub fn dimension<'d>(&'d mut self, name: &str, len: usize) -> error::Result<Dimension<'d>> {
+        super::dimension::add_dimension_at(self.ncid, name, len)
+    }
+    /// Add a dimension with unbounded size
+    pub fn unlimited_dimension<'d>(&'d mut self, name: &str) -> error::Result<Dimension<'d>> {
+        self.dimension(name, 0)
+    }
+    /// Add a variable, see [`MutableFile::add_variable`]
+    pub fn variable<'d, T>(
+        &'d mut self,
+        name: &str,
+        dims: &[&str],
+    ) -> error::Result<VariableMut<'d>>
+    where
+        T: NcPutGet,
+    {
+        let mut var = VariableMut::add_from_str(self.ncid, T::NCTYPE, name, dims)?;
+        var.apply_default_compression(self.compression)?;
+        Ok(var)
+    }
+    /// Add an attribute to the root group
+    pub fn attribute<'d, T>(&'d mut self, name: &str, val: T) -> error::Result<Attribute<'d>>
+    where
+        T: Into<AttrValue>,
+    {
+        Attribute::put(self.ncid, NC_GLOBAL, name, val.into())
     }
 }
 
@@ -414,3 +1533,106 @@ impl<'a> std::ops::Deref for MemFile<'a> {
         &self.0
     }
 }
+
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+/// A writable `netCDF` file which only lives in memory
+///
+/// Access a [`MutableFile`] through the `Deref` traits, and
+/// retrieve the serialized file with [`MemFileMut::finish`]
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut file = netcdf::create_mem(None, 0)?;
+/// file.add_dimension("x", 4)?;
+/// let bytes: Vec<u8> = file.into_bytes()?;
+/// # Ok(()) }
+/// ```
+#[allow(clippy::module_name_repetitions)]
+pub struct MemFileMut(MutableFile);
+
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+impl std::ops::Deref for MemFileMut {
+    type Target = MutableFile;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+impl std::ops::DerefMut for MemFileMut {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+impl MemFileMut {
+    /// Read only access to the file, e.g. for inspecting what
+    /// has been written before calling [`finish`](Self::finish)
+    ///
+    /// Classic files must leave define mode with
+    /// [`MutableFile::enddef`] before their data can be read
+    pub fn as_read(&self) -> &File {
+        &self.0.file
+    }
+
+    /// Write out all pending changes and close the file,
+    /// returning the complete contents of the file
+    ///
+    /// The buffer of `netCDF` is copied into the returned `Vec` and released,
+    /// the bytes are owned by the caller and stay valid after the file is
+    /// gone. They can be written to disk, or reopened with
+    /// [`open_mem`](crate::open_mem) to continue reading
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create_mem(None, 0)?;
+    /// file.add_dimension("x", 3)?;
+    /// file.add_variable::<f64>("v", &["x"])?.put_values(&[1.0, 2.0, 3.0], ..)?;
+    /// assert!(file.as_read().variable("v").is_some());
+    /// let bytes = file.finish()?;
+    /// let file = netcdf::open_mem(None, &bytes)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The file could not be written, e.g. invalid definitions
+    pub fn finish(self) -> error::Result<Vec<u8>> {
+        self.into_bytes()
+    }
+
+    /// Close the file, returning the complete contents of the file,
+    /// see [`finish`](Self::finish)
+    pub fn into_bytes(self) -> error::Result<Vec<u8>> {
+        extern "C" {
+            // The buffer is allocated by `netCDF` through `malloc`
+            fn free(ptr: *mut std::os::raw::c_void);
+        }
+
+        let ncid = self.ncid();
+        // The file is closed below, avoid a second close through `RawFile`
+        std::mem::forget(self);
+
+        let mut memio = NC_memio {
+            size: 0,
+            memory: std::ptr::null_mut(),
+            flags: 0,
+        };
+        unsafe {
+            error::checked(super::with_close_lock(ncid, || {
+                nc_close_memio(ncid, &mut memio)
+            }))?;
+        }
+        if memio.memory.is_null() {
+            return Ok(Vec::new());
+        }
+        let bytes = unsafe {
+            let bytes = std::slice::from_raw_parts(memio.memory.cast::<u8>(), memio.size).to_vec();
+            free(memio.memory);
+            bytes
+        };
+        Ok(bytes)
+    }
+}
@@ -5,7 +5,7 @@ use super::attribute::AttrValue;
 use super::attribute::Attribute;
 use super::dimension::Dimension;
 use super::error;
-use super::variable::{NcPutGet, Variable, VariableMut};
+use super::variable::{NcPutGet, Variable, VariableBuilder, VariableMut};
 use netcdf_sys::*;
 use std::convert::TryInto;
 use std::marker::PhantomData;
@@ -22,15 +22,18 @@ pub struct Group<'f> {
 #[derive(Debug)]
 /// Mutable access to a group
 #[allow(clippy::module_name_repetitions)]
-pub struct GroupMut<'f>(
-    pub(crate) Group<'f>,
-    pub(crate) PhantomData<&'f mut nc_type>,
-);
+pub struct GroupMut<'f> {
+    group: Group<'f>,
+    /// Deflate level and shuffle of new variables, see
+    /// [`MutableFile::set_default_compression`](crate::MutableFile::set_default_compression)
+    default_compression: Option<(u8, bool)>,
+    _file: PhantomData<&'f mut nc_type>,
+}
 
 impl<'f> std::ops::Deref for GroupMut<'f> {
     type Target = Group<'f>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.group
     }
 }
 
@@ -49,6 +52,49 @@ impl<'f> Group<'f> {
 
         String::from_utf8(name).expect("Group did not have a valid name")
     }
+    /// Full path of the group, such as `/model/ocean`,
+    /// the root group has the path `/`
+    ///
+    /// # Errors
+    ///
+    /// Not a valid group, or the path is not valid utf-8
+    pub fn path(&self) -> error::Result<String> {
+        let mut len = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_grpname_full(self.ncid, &mut len, std::ptr::null_mut())
+            }))?;
+        }
+        let mut path = vec![0_u8; len + 1];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_grpname_full(self.ncid, std::ptr::null_mut(), path.as_mut_ptr().cast())
+            }))?;
+        }
+        path.truncate(len);
+        Ok(String::from_utf8(path)?)
+    }
+    /// Parent of this group, `None` for the root group
+    ///
+    /// Together with [`groups`](Self::groups) this allows walking the
+    /// hierarchy in both directions, e.g. to look for coordinate
+    /// variables in the enclosing groups
+    ///
+    /// # Errors
+    ///
+    /// Not a valid group, or netcdf error
+    pub fn parent(&self) -> error::Result<Option<Group<'f>>> {
+        let mut parent = 0;
+        let e = unsafe { super::with_lock(|| nc_inq_grp_parent(self.ncid, &mut parent)) };
+        if e == NC_ENOGRP {
+            return Ok(None);
+        }
+        error::checked(e)?;
+        Ok(Some(Group {
+            ncid: parent,
+            _file: PhantomData,
+        }))
+    }
     /// Internal ncid of the group
     fn id(&self) -> nc_type {
         self.ncid
@@ -70,6 +116,21 @@ impl<'f> Group<'f> {
             .unwrap()
             .map(Result::unwrap)
     }
+    /// Variables of the group in the order they were defined, see
+    /// [`File::variables_in_creation_order`](crate::File::variables_in_creation_order)
+    ///
+    /// # Errors
+    ///
+    /// The first error while reading the variables
+    pub fn variables_in_creation_order<'g>(&'g self) -> error::Result<Vec<Variable<'g>>>
+    where
+        'f: 'g,
+    {
+        let mut variables =
+            super::variable::variables_at_ncid(self.id())?.collect::<error::Result<Vec<_>>>()?;
+        variables.sort_by_key(|var| var.varid);
+        Ok(variables)
+    }
 
     /// Get a single attribute
     pub fn attribute<'a>(&'a self, name: &str) -> Option<Attribute<'a>> {
@@ -82,6 +143,53 @@ impl<'f> Group<'f> {
             .unwrap()
             .map(Result::unwrap)
     }
+    /// Number of variables in the group
+    ///
+    /// This is cheaper than counting the variables from an iterator
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn num_variables(&self) -> error::Result<usize> {
+        let mut nvars = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_nvars(self.ncid, &mut nvars)))?;
+        }
+        Ok(nvars.try_into()?)
+    }
+    /// Number of dimensions defined in the group, excluding
+    /// the dimensions of the parents
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn num_dimensions(&self) -> error::Result<usize> {
+        let mut ndims = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_ndims(self.ncid, &mut ndims)))?;
+        }
+        Ok(ndims.try_into()?)
+    }
+    /// Number of attributes of the group
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF` file, or netcdf error
+    pub fn num_attributes(&self) -> error::Result<usize> {
+        let mut natts = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_natts(self.ncid, &mut natts)))?;
+        }
+        Ok(natts.try_into()?)
+    }
+    /// Read the names and values of all attributes in the group
+    ///
+    /// # Errors
+    ///
+    /// Not a netCDF group, or an attribute with a type which can not be read
+    pub fn attribute_map(&self) -> error::Result<Vec<(String, AttrValue)>> {
+        crate::attribute::attribute_map(self.ncid, None)
+    }
 
     /// Get a single dimension
     pub fn dimension<'g>(&'g self, name: &str) -> Option<Dimension<'g>>
@@ -99,6 +207,13 @@ impl<'f> Group<'f> {
             .unwrap()
             .map(Result::unwrap)
     }
+    /// Iterator over all unlimited dimensions
+    pub fn unlimited_dimensions<'g>(&'g self) -> impl Iterator<Item = Dimension<'g>>
+    where
+        'f: 'g,
+    {
+        self.dimensions().filter(Dimension::is_unlimited)
+    }
 
     /// Get a group
     pub fn group<'g>(&'g self, name: &str) -> Option<Group<'g>>
@@ -145,14 +260,18 @@ impl<'f> GroupMut<'f> {
     where
         'f: 'g,
     {
-        self.group(name).map(|g| GroupMut(g, PhantomData))
+        let default_compression = self.default_compression;
+        self.group(name)
+            .map(|g| GroupMut::new(g, default_compression))
     }
     /// Iterator over all groups (mutable access)
     pub fn groups_mut<'g>(&'g mut self) -> impl Iterator<Item = GroupMut<'g>>
     where
         'f: 'g,
     {
-        self.groups().map(|g| GroupMut(g, PhantomData))
+        let default_compression = self.default_compression;
+        self.groups()
+            .map(move |g| GroupMut::new(g, default_compression))
     }
 
     /// Add an opaque datatype, with `size` bytes
@@ -197,7 +316,42 @@ impl<'f> GroupMut<'f> {
         Attribute::put(self.ncid, NC_GLOBAL, name, val.into())
     }
 
+    /// Add an attribute with the exact type of `val`, see [`add_attribute`](Self::add_attribute)
+    pub fn add_attribute_typed<'a>(
+        &'a mut self,
+        name: &str,
+        val: AttrValue,
+    ) -> error::Result<Attribute<'a>> {
+        self.add_attribute(name, val)
+    }
+
+    /// Delete an attribute from this group
+    ///
+    /// # Errors
+    ///
+    /// `NC_ENOTATT` if the attribute does not exist
+    pub fn del_attribute(&mut self, name: &str) -> error::Result<()> {
+        Attribute::delete(self.ncid, NC_GLOBAL, name)
+    }
+
+    /// Rename an attribute of this group, keeping its value
+    ///
+    /// Files in the classic format which are not in define mode
+    /// will temporarily reenter define mode if this is required
+    ///
+    /// # Errors
+    ///
+    /// `NC_ENOTATT` if the attribute does not exist, or `new` is already
+    /// in use ([`NameInUse`](error::Error::NameInUse))
+    pub fn rename_attribute(&mut self, old: &str, new: &str) -> error::Result<()> {
+        Attribute::rename(self.ncid, NC_GLOBAL, old, new)
+    }
+
     /// Adds a dimension with the given name and size. A size of zero gives an unlimited dimension
+    ///
+    /// The dimension belongs to this group, and can be used by variables
+    /// in this group and its subgroups, shadowing dimensions of the same
+    /// name in the parents
     pub fn add_dimension<'g>(&'g mut self, name: &str, len: usize) -> error::Result<Dimension<'g>> {
         super::dimension::add_dimension_at(self.id(), name, len)
     }
@@ -207,21 +361,94 @@ impl<'f> GroupMut<'f> {
         self.add_dimension(name, 0)
     }
 
-    pub(crate) fn add_group_at(ncid: nc_type, name: &str) -> error::Result<Self> {
+    /// Remove a variable from the group
+    ///
+    /// `netCDF` has no way of deleting a variable once it has been
+    /// defined, this always fails. To drop variables, copy the
+    /// variables which should be kept to a new file, e.g. using
+    /// [`MutableFile::copy_variable_from`](crate::MutableFile::copy_variable_from)
+    ///
+    /// # Errors
+    ///
+    /// [`NotFound`](error::Error::NotFound) if there is no such variable,
+    /// otherwise [`Unsupported`](error::Error::Unsupported)
+    pub fn remove_variable(&mut self, name: &str) -> error::Result<()> {
+        if Variable::find_from_name(self.id(), name)?.is_none() {
+            return Err(error::Error::NotFound(format!("variable {name}")));
+        }
+        Err(error::Error::Unsupported(
+            "netCDF does not support deleting variables, copy the remaining variables to a new file instead",
+        ))
+    }
+
+    /// Rename a dimension in this group
+    ///
+    /// Rename coordinate variables and dimensions before writing
+    /// data, `netCDF-4` files may otherwise lose the written data
+    ///
+    /// # Errors
+    ///
+    /// No dimension named `old`, or `new` is already in use
+    /// ([`NameInUse`](error::Error::NameInUse))
+    pub fn rename_dimension(&mut self, old: &str, new: &str) -> error::Result<()> {
+        super::dimension::rename_dimension_at(self.id(), old, new)
+    }
+
+    pub(crate) fn new(group: Group<'f>, default_compression: Option<(u8, bool)>) -> Self {
+        Self {
+            group,
+            default_compression,
+            _file: PhantomData,
+        }
+    }
+
+    pub(crate) fn add_group_at(
+        ncid: nc_type,
+        name: &str,
+        default_compression: Option<(u8, bool)>,
+    ) -> error::Result<Self> {
         let byte_name = super::utils::short_name_to_bytes(name)?;
         let mut grpid = 0;
         unsafe {
-            error::checked(super::with_lock(|| {
-                nc_def_grp(ncid, byte_name.as_ptr().cast(), &mut grpid)
-            }))?;
+            error::checked_name(
+                super::with_lock(|| nc_def_grp(ncid, byte_name.as_ptr().cast(), &mut grpid)),
+                "group",
+                name,
+            )?;
+        }
+
+        Ok(Self::new(
+            Group {
+                ncid: grpid,
+                _file: PhantomData,
+            },
+            default_compression,
+        ))
+    }
+
+    /// Walks `path` from `ncid`, creating the groups which does not yet exist
+    pub(crate) fn add_group_path_at(
+        ncid: nc_type,
+        path: &str,
+        default_compression: Option<(u8, bool)>,
+    ) -> error::Result<Self> {
+        let mut grpid = ncid;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            grpid = match group_from_name(grpid, name)? {
+                Some(group) => group.ncid,
+                None => Self::add_group_at(grpid, name, default_compression)?.ncid,
+            };
+        }
+        if grpid == ncid {
+            return Err(format!("path {path:?} does not contain any group names").into());
         }
 
-        Ok(Self(
+        Ok(Self::new(
             Group {
                 ncid: grpid,
                 _file: PhantomData,
             },
-            PhantomData,
+            default_compression,
         ))
     }
 
@@ -230,7 +457,7 @@ impl<'f> GroupMut<'f> {
     where
         'f: 'g,
     {
-        Self::add_group_at(self.id(), name)
+        Self::add_group_at(self.id(), name, self.default_compression)
     }
 
     /// Create a Variable into the dataset, with no data written into it
@@ -246,7 +473,9 @@ impl<'f> GroupMut<'f> {
         T: NcPutGet,
         'f: 'g,
     {
-        VariableMut::add_from_str(self.id(), T::NCTYPE, name, dims)
+        let mut var = VariableMut::add_from_str(self.id(), T::NCTYPE, name, dims)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
     }
     /// Adds a variable with a basic type of string
     pub fn add_string_variable<'g>(
@@ -266,7 +495,60 @@ impl<'f> GroupMut<'f> {
     where
         T: NcPutGet,
     {
-        super::variable::add_variable_from_identifiers(self.id(), name, dims, T::NCTYPE)
+        let mut var =
+            super::variable::add_variable_from_identifiers(self.id(), name, dims, T::NCTYPE)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
+    }
+    /// Adds a variable using these dimensions, which may be
+    /// defined in this group or any of the parent groups
+    ///
+    /// Unlike [`add_variable`](Self::add_variable) this is not
+    /// ambiguous when dimensions in different groups share a name
+    ///
+    /// # Errors
+    ///
+    /// Dimensions belong to another file, or netcdf error
+    pub fn add_variable_dims<'g, T>(
+        &'g mut self,
+        name: &str,
+        dims: &[Dimension],
+    ) -> error::Result<VariableMut<'g>>
+    where
+        T: NcPutGet,
+    {
+        let ids = dims.iter().map(Dimension::identifier).collect::<Vec<_>>();
+        let mut var =
+            super::variable::add_variable_from_identifiers(self.id(), name, &ids, T::NCTYPE)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
+    }
+
+    /// Configure a variable with a builder, see
+    /// [`MutableFile::variable_builder`](crate::MutableFile::variable_builder)
+    pub fn variable_builder<'g, T>(&'g mut self, name: &str) -> VariableBuilder<'g, T>
+    where
+        T: NcPutGet,
+    {
+        VariableBuilder::new(self.id(), name).default_compression(self.default_compression)
+    }
+
+    /// Create a variable with the type given by its `netCDF` type id,
+    /// e.g. `NC_STRING`, or the id of a user defined type
+    ///
+    /// # Errors
+    ///
+    /// [`TypeUnknown`](error::Error::TypeUnknown) if there is no such
+    /// type, missing dimensions, or netcdf error
+    pub fn add_variable_with_type_id<'g>(
+        &'g mut self,
+        name: &str,
+        dims: &[&str],
+        xtype: nc_type,
+    ) -> error::Result<VariableMut<'g>> {
+        let mut var = VariableMut::add_from_type_id(self.id(), xtype, name, dims)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
     }
 
     /// Create a variable with the specified type
@@ -276,7 +558,9 @@ impl<'f> GroupMut<'f> {
         dims: &[&str],
         typ: &super::types::VariableType,
     ) -> error::Result<VariableMut<'f>> {
-        VariableMut::add_from_str(self.id(), typ.id(), name, dims)
+        let mut var = VariableMut::add_from_str(self.id(), typ.id(), name, dims)?;
+        var.apply_default_compression(self.default_compression)?;
+        Ok(var)
     }
 }
 
@@ -299,6 +583,42 @@ pub(crate) fn groups_at_ncid<'f>(ncid: nc_type) -> error::Result<impl Iterator<I
     }))
 }
 
+/// Depth first traversal of all variables in the group tree below `ncid`,
+/// paired with the path of the group containing the variable
+pub(crate) fn variables_recursive_at<'f>(
+    ncid: nc_type,
+) -> impl Iterator<Item = error::Result<(String, Variable<'f>)>> {
+    // An explicit stack avoids overflows on deeply nested files
+    let mut stack = vec![(String::from("/"), ncid)];
+    std::iter::from_fn(move || {
+        let (path, ncid) = stack.pop()?;
+        let mut items = Vec::new();
+        match groups_at_ncid(ncid) {
+            Ok(groups) => {
+                let children = groups
+                    .map(|group| {
+                        let name = group.name();
+                        let child = if path == "/" {
+                            format!("/{name}")
+                        } else {
+                            format!("{path}/{name}")
+                        };
+                        (child, group.ncid)
+                    })
+                    .collect::<Vec<_>>();
+                stack.extend(children.into_iter().rev());
+            }
+            Err(e) => items.push(Err(e)),
+        }
+        match super::variable::variables_at_ncid(ncid) {
+            Ok(vars) => items.extend(vars.map(|var| var.map(|var| (path.clone(), var)))),
+            Err(e) => items.push(Err(e)),
+        }
+        Some(items)
+    })
+    .flatten()
+}
+
 pub(crate) fn group_from_name<'f>(ncid: nc_type, name: &str) -> error::Result<Option<Group<'f>>> {
     let byte_name = super::utils::short_name_to_bytes(name)?;
     let mut grpid = 0;
@@ -315,3 +635,18 @@ pub(crate) fn group_from_name<'f>(ncid: nc_type, name: &str) -> error::Result<Op
         _file: PhantomData,
     }))
 }
+
+pub(crate) fn group_from_path<'f>(ncid: nc_type, path: &str) -> error::Result<Option<Group<'f>>> {
+    let cpath = std::ffi::CString::new(path)?;
+    let mut grpid = 0;
+    let e = unsafe { super::with_lock(|| nc_inq_grp_full_ncid(ncid, cpath.as_ptr(), &mut grpid)) };
+    if e == NC_ENOGRP {
+        return Ok(None);
+    }
+    error::checked(e)?;
+
+    Ok(Some(Group {
+        ncid: grpid,
+        _file: PhantomData,
+    }))
+}
@@ -88,15 +88,20 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 use lazy_static::lazy_static;
-use netcdf_sys::nc_type;
-use std::sync::Mutex;
+use netcdf_sys::{nc_get_chunk_cache, nc_inq_libvers, nc_set_chunk_cache, nc_type};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
 
 pub mod attribute;
+#[cfg(feature = "complex")]
+mod complex;
 pub mod dimension;
 pub mod error;
 pub mod extent;
 pub mod file;
 pub mod group;
+#[cfg(feature = "time")]
+mod time;
 pub mod types;
 pub mod variable;
 
@@ -113,7 +118,7 @@ pub fn create<P>(name: P) -> error::Result<MutableFile>
 where
     P: AsRef<std::path::Path>,
 {
-    RawFile::create_with(name.as_ref(), Options::NETCDF4)
+    OpenOptions::new().create(name)
 }
 
 /// Open a `netCDF` file in create mode with the given options
@@ -129,7 +134,7 @@ pub fn append<P>(name: P) -> error::Result<MutableFile>
 where
     P: AsRef<std::path::Path>,
 {
-    append_with(name, Options::default())
+    OpenOptions::new().append(name)
 }
 
 /// Open a `netCDF` file in append mode with the given options
@@ -145,7 +150,7 @@ pub fn open<P>(name: P) -> error::Result<File>
 where
     P: AsRef<std::path::Path>,
 {
-    open_with(name, Options::default())
+    OpenOptions::new().open(name)
 }
 
 /// Open a `netCDF` file in read mode with the given options
@@ -156,27 +161,260 @@ where
     RawFile::open_with(name.as_ref(), options)
 }
 
+#[cfg(target_os = "linux")]
+/// Open a `netCDF` file in read mode from an open file descriptor
+///
+/// `netCDF` can only open files by name, so the file is opened through
+/// the link `/proc/self/fd/<fd>`, which is why this is only available
+/// on Linux. `/proc` must be mounted, also in sandboxes. The library
+/// opens a descriptor of its own, `fd` stays owned by the caller and
+/// may be closed after this call. [`File::path`] gives the `/proc` link
+///
+/// # Errors
+///
+/// `fd` is not an open descriptor of a `netCDF` file, or `/proc` is missing
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::os::unix::io::AsRawFd;
+/// // A descriptor handed over by a supervisor process
+/// let handle = std::fs::File::open("data.nc")?;
+/// let file = netcdf::open_fd(handle.as_raw_fd())?;
+/// # Ok(()) }
+/// ```
+pub fn open_fd(fd: std::os::unix::io::RawFd) -> error::Result<File> {
+    open_fd_with(fd, Options::default())
+}
+
+#[cfg(target_os = "linux")]
+/// Open a `netCDF` file from an open file descriptor with the given
+/// options, see [`open_fd`]
+pub fn open_fd_with(fd: std::os::unix::io::RawFd, options: Options) -> error::Result<File> {
+    if fd < 0 {
+        return Err(format!("invalid file descriptor {fd}").into());
+    }
+    RawFile::open_with(format!("/proc/self/fd/{fd}").as_ref(), options)
+}
+
+/// Open a remote dataset using `OPeNDAP`
+///
+/// The `url` (e.g. `http://`, `https://` or `dap4://`) is given
+/// as is to the `netCDF` library, which must have been built with
+/// `DAP` support. The `dap` feature of this crate ensures this at build time.
+///
+/// # Errors
+///
+/// `netCDF` was built without `DAP` support, `url` contains
+/// null bytes, or the dataset could not be opened
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let file = netcdf::open_dap("https://example.com/thredds/dodsC/dataset.nc")?;
+/// for var in file.variables() {
+///     println!("{}", var.name());
+/// }
+/// # Ok(()) }
+/// ```
+pub fn open_dap(url: &str) -> error::Result<File> {
+    RawFile::open_dap(url, Options::default())
+}
+
 #[cfg(feature = "has-mmap")]
 /// Open a `netCDF` file from a buffer
 pub fn open_mem<'a>(name: Option<&str>, mem: &'a [u8]) -> error::Result<MemFile<'a>> {
     RawFile::open_from_memory(name, mem)
 }
 
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+/// Create a `netCDF4` file which is kept in memory
+///
+/// `initial_size` is a hint for the size of the initial buffer.
+/// The serialized file is retrieved using [`MemFileMut::finish`]
+pub fn create_mem(name: Option<&str>, initial_size: usize) -> error::Result<MemFileMut> {
+    create_mem_with(name, initial_size, Options::NETCDF4)
+}
+
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+/// Create a `netCDF` file which is kept in memory, with the given options
+pub fn create_mem_with(
+    name: Option<&str>,
+    initial_size: usize,
+    options: Options,
+) -> error::Result<MemFileMut> {
+    RawFile::create_in_memory(name, initial_size, options)
+}
+
+/// Set the default chunk cache `(size, nelems, preemption)` used
+/// for `netCDF-4` files
+///
+/// This is a process-wide setting, and only affects files opened or
+/// created after the call. Call this before opening the files to tune.
+/// See [`VariableMut::set_chunk_cache`] for the meaning of the parameters,
+/// and for changing the cache of a single variable
+///
+/// # Errors
+///
+/// `preemption` outside `0.0..=1.0`, or netcdf error
+pub fn set_chunk_cache(size: usize, nelems: usize, preemption: f32) -> error::Result<()> {
+    if !(0.0..=1.0).contains(&preemption) {
+        return Err(format!("preemption {preemption} must be between 0.0 and 1.0").into());
+    }
+    unsafe { error::checked(with_lock(|| nc_set_chunk_cache(size, nelems, preemption))) }
+}
+
+/// Get the default chunk cache `(size, nelems, preemption)`
+/// used when opening `netCDF-4` files, see [`set_chunk_cache`]
+pub fn get_chunk_cache() -> error::Result<(usize, usize, f32)> {
+    let mut size = 0;
+    let mut nelems = 0;
+    let mut preemption = 0.0;
+    unsafe {
+        error::checked(with_lock(|| {
+            nc_get_chunk_cache(&mut size, &mut nelems, &mut preemption)
+        }))?;
+    }
+    Ok((size, nelems, preemption))
+}
+
+/// Version of the linked `netCDF` library
+///
+/// This is the full version string reported by the library,
+/// e.g. `"4.9.2 of Mar 14 2023 12:00:00 $"`
+///
+/// # Example
+///
+/// ```rust
+/// println!("Using netCDF {}", netcdf::library_version());
+/// ```
+pub fn library_version() -> &'static str {
+    // The library returns a pointer to a static string, no lock
+    // is needed as no state is modified
+    let version = unsafe { std::ffi::CStr::from_ptr(nc_inq_libvers()) };
+    version.to_str().unwrap_or("unknown")
+}
+
+/// Optional capabilities of the linked `netCDF` library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Feature {
+    /// Remote access to datasets through `DAP`, see [`open_dap`]
+    Dap,
+    /// Files kept in memory, see `open_mem` and `create_mem`
+    Mmap,
+    /// Writing variables compressed with `szip`
+    Szip,
+    /// Parallel `I/O` through `MPI`
+    Parallel,
+}
+
+/// Whether the linked `netCDF` library supports `feature`
+///
+/// # Example
+///
+/// ```rust
+/// use netcdf::Feature;
+/// if !netcdf::has_feature(Feature::Dap) {
+///     eprintln!("remote datasets are not available");
+/// }
+/// ```
+pub fn has_feature(feature: Feature) -> bool {
+    match feature {
+        Feature::Dap => cfg!(feature = "has-dap"),
+        Feature::Mmap => cfg!(feature = "has-mmap"),
+        Feature::Szip => cfg!(feature = "has-szip"),
+        Feature::Parallel => cfg!(feature = "has-parallel"),
+    }
+}
+
 lazy_static! {
-    /// Use this when accessing `netCDF` functions
-    pub(crate) static ref LOCK: Mutex<()> = Mutex::new(());
+    /// Use this when accessing `netCDF` functions. It also holds the
+    /// locks of files which allow concurrent reads, keyed by the id
+    /// of the root group, see [`with_read_lock`]
+    pub(crate) static ref LOCK: RwLock<HashMap<nc_type, Mutex<()>>> = RwLock::new(HashMap::new());
 }
 
 /// All functions should be wrapped in this locker. Disregarding this, expect
 /// segfaults, especially on non-threadsafe hdf5 builds
 pub(crate) fn with_lock<F: FnMut() -> nc_type>(mut f: F) -> nc_type {
-    let _l = LOCK.lock().unwrap();
+    let _l = LOCK.write().unwrap();
     f()
 }
 
+/// Locker for closing the file `ncid`, which forgets
+/// the lock of the file used for concurrent reads
+pub(crate) fn with_close_lock<F: FnMut() -> nc_type>(ncid: nc_type, mut f: F) -> nc_type {
+    let mut files = LOCK.write().unwrap();
+    files.remove(&ncid);
+    f()
+}
+
+#[cfg(feature = "thread-safe-hdf5")]
+lazy_static! {
+    /// The linked `hdf5` library was built with thread safety
+    pub(crate) static ref HDF5_THREADSAFE: bool = {
+        let mut is_ts: hdf5_sys::h5::hbool_t = 0;
+        let err = unsafe { hdf5_sys::h5::H5is_library_threadsafe(&mut is_ts) };
+        err >= 0 && is_ts != 0
+    };
+}
+
+/// The lower bits of the id of a group in a `netCDF-4`
+/// file, the upper bits identify the file
+#[cfg(feature = "thread-safe-hdf5")]
+const GRP_ID_MASK: nc_type = 0xFFFF;
+
+/// Locker for functions which only read variable data from the group `ncid`
+///
+/// Files which allow concurrent reads (see `File::enable_concurrent_reads`)
+/// share the global lock with other such reads, and lock only the file
+/// itself. Other calls have exclusive use of the library as in [`with_lock`]
+#[cfg_attr(not(feature = "thread-safe-hdf5"), allow(unused_mut, unused_variables))]
+pub(crate) fn with_read_lock<F: FnMut() -> nc_type>(ncid: nc_type, mut f: F) -> nc_type {
+    #[cfg(feature = "thread-safe-hdf5")]
+    {
+        let files = LOCK.read().unwrap();
+        if let Some(file) = files.get(&(ncid & !GRP_ID_MASK)) {
+            let _l = file.lock().unwrap();
+            return f();
+        }
+    }
+    with_lock(f)
+}
+
 pub(crate) mod utils {
     use super::error;
-    use netcdf_sys::{NC_EMAXNAME, NC_MAX_NAME};
+    use netcdf_sys::{nc_enddef, nc_redef, nc_type, NC_EMAXNAME, NC_ENOTINDEFINE, NC_MAX_NAME};
+
+    /// Calls `f`, temporarily reentering define mode if this is
+    /// required by the operation (classic files)
+    pub(crate) fn checked_in_define_mode<F: FnMut() -> nc_type>(
+        ncid: nc_type,
+        f: F,
+    ) -> error::Result<()> {
+        error::checked(in_define_mode(ncid, f)?)
+    }
+
+    /// As [`checked_in_define_mode`], but returns the status of `f`
+    pub(crate) fn in_define_mode<F: FnMut() -> nc_type>(
+        ncid: nc_type,
+        mut f: F,
+    ) -> error::Result<nc_type> {
+        let e = super::with_lock(&mut f);
+        if e != NC_ENOTINDEFINE {
+            return Ok(e);
+        }
+        unsafe {
+            error::checked(super::with_lock(|| nc_redef(ncid)))?;
+            let e = super::with_lock(&mut f);
+            error::checked(super::with_lock(|| nc_enddef(ncid)))?;
+            Ok(e)
+        }
+    }
+
     /// Use this function for short `netCDF` names to avoid the allocation
     /// for a `CString`
     pub(crate) fn short_name_to_bytes(name: &str) -> error::Result<[u8; NC_MAX_NAME as usize + 1]> {
@@ -0,0 +1,175 @@
+//! Decoding of time coordinates following the CF conventions
+
+use super::error;
+use super::Variable;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+
+/// First day of the Gregorian calendar, the `standard` calendar
+/// switches to the Julian calendar before this date
+const GREGORIAN_START: (i32, u32, u32) = (1582, 10, 15);
+
+impl<'g> Variable<'g> {
+    /// Read the values of a time coordinate as dates
+    ///
+    /// The `units` attribute must be of the form `<unit> since <epoch>`,
+    /// e.g. `hours since 2000-01-01 00:00:00`, where the unit is one of
+    /// `days`, `hours`, `minutes`, `seconds`, `milliseconds` or
+    /// `microseconds`. The epoch may include a time, fractional seconds
+    /// and an offset from UTC. Values are rounded to microseconds
+    ///
+    /// The `calendar` attribute may be `standard` (the default when the
+    /// attribute is missing), `gregorian` or `proleptic_gregorian`. The
+    /// mixed Julian/Gregorian `standard` calendar is only supported for
+    /// dates from 1582-10-15, where it agrees with `proleptic_gregorian`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?;
+    /// let time = file.variable("time").unwrap();
+    /// for date in time.time_values()? {
+    ///     println!("{}", date.to_rfc3339());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Missing or invalid `units`, unsupported calendars such as `360_day`,
+    /// values which can not be represented as dates, or netcdf error
+    pub fn time_values(&self) -> error::Result<Vec<DateTime<Utc>>> {
+        let units = match self.attribute("units") {
+            Some(units) => units.text()?,
+            None => return Err("time variable has no units".into()),
+        };
+        let calendar = match self.attribute("calendar") {
+            Some(calendar) => calendar.text()?.to_lowercase(),
+            None => "standard".to_owned(),
+        };
+        let mixed = match calendar.as_str() {
+            "standard" | "gregorian" => true,
+            "proleptic_gregorian" => false,
+            _ => return Err(format!("unsupported calendar {calendar}").into()),
+        };
+        let (unit, epoch) = parse_units(&units)?;
+        let gregorian_start = {
+            let (y, m, d) = GREGORIAN_START;
+            Utc.from_utc_datetime(&NaiveDate::from_ymd_opt(y, m, d).unwrap().into())
+        };
+
+        self.values::<f64, _>(..)?
+            .into_iter()
+            .map(|value| {
+                let micros = value * unit;
+                #[allow(clippy::cast_precision_loss)]
+                let in_range = micros.is_finite() && micros.abs() < i64::MAX as f64;
+                if !in_range {
+                    return Err(format!("time value {value} is out of range").into());
+                }
+                #[allow(clippy::cast_possible_truncation)]
+                let offset = Duration::microseconds(micros.round() as i64);
+                let date = epoch
+                    .checked_add_signed(offset)
+                    .ok_or_else(|| format!("time value {value} is out of range"))?;
+                if mixed && date < gregorian_start {
+                    return Err(format!(
+                        "time value {value} is before {gregorian_start}, \
+                         which is not supported for the {calendar} calendar"
+                    )
+                    .into());
+                }
+                Ok(date)
+            })
+            .collect()
+    }
+}
+
+/// Split `<unit> since <epoch>` into the length of
+/// the unit in microseconds and the epoch
+fn parse_units(units: &str) -> error::Result<(f64, DateTime<Utc>)> {
+    let invalid = || error::Error::from(format!("invalid time units {units}"));
+    let mut parts = units.trim().splitn(3, char::is_whitespace);
+    let unit = parts.next().ok_or_else(invalid)?.to_lowercase();
+    if !parts
+        .next()
+        .is_some_and(|since| since.eq_ignore_ascii_case("since"))
+    {
+        return Err(invalid());
+    }
+    let epoch = parts.next().ok_or_else(invalid)?;
+
+    let micros = match unit.as_str() {
+        "days" | "day" | "d" => 86_400e6,
+        "hours" | "hour" | "hrs" | "hr" | "h" => 3_600e6,
+        "minutes" | "minute" | "mins" | "min" => 60e6,
+        "seconds" | "second" | "secs" | "sec" | "s" => 1e6,
+        "milliseconds" | "millisecond" | "msecs" | "msec" | "ms" => 1e3,
+        "microseconds" | "microsecond" | "usecs" | "usec" | "us" => 1.0,
+        _ => return Err(format!("unsupported time unit {unit}").into()),
+    };
+    let epoch = parse_epoch(epoch.trim()).ok_or_else(invalid)?;
+    Ok((micros, epoch))
+}
+
+/// Parse a date such as `2000-01-01`, `2000-1-1 12:00:00.5`,
+/// `2000-01-01T12:00:00Z` or `2000-01-01 12:00 -06:00`
+fn parse_epoch(epoch: &str) -> Option<DateTime<Utc>> {
+    let epoch = epoch
+        .strip_suffix("UTC")
+        .or_else(|| epoch.strip_suffix('Z'))
+        .unwrap_or(epoch)
+        .trim_end();
+    let (date, rest) = match epoch.find(['T', ' ']) {
+        Some(i) => (&epoch[..i], epoch[i + 1..].trim()),
+        None => (epoch, ""),
+    };
+
+    let mut ymd = date.splitn(3, '-');
+    let year = ymd.next()?.parse().ok()?;
+    let month = ymd.next()?.parse().ok()?;
+    let day = ymd.next()?.parse().ok()?;
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+
+    // The time is followed by an optional offset from UTC
+    let (time, offset) = match rest.find(['+', '-']) {
+        Some(i) => (rest[..i].trim(), Some(&rest[i..])),
+        None => (rest, None),
+    };
+    let mut hms = time.splitn(3, ':');
+    let hour = match hms.next() {
+        Some("") | None => 0,
+        Some(hour) => hour.parse().ok()?,
+    };
+    let minute = hms.next().map_or(Some(0), |m| m.parse().ok())?;
+    let second: f64 = hms.next().map_or(Some(0.0), |s| s.parse().ok())?;
+    if !(0.0..60.0).contains(&second) {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let nanos = ((second.fract() * 1e9).round() as u32).min(999_999_999);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let datetime = date.and_hms_nano_opt(hour, minute, second.trunc() as u32, nanos)?;
+
+    let offset_minutes = match offset {
+        None => 0,
+        Some(offset) => {
+            let (sign, offset) = match offset.strip_prefix('-') {
+                Some(offset) => (-1, offset),
+                None => (1, offset.strip_prefix('+').unwrap_or(offset)),
+            };
+            let (hours, minutes) = match offset.split_once(':') {
+                Some((h, m)) => (h.parse::<i64>().ok()?, m.parse::<i64>().ok()?),
+                None if offset.len() == 4 => (offset[..2].parse().ok()?, offset[2..].parse().ok()?),
+                None => (offset.parse().ok()?, 0),
+            };
+            // Offsets from UTC are less than a day
+            if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+                return None;
+            }
+            sign * (hours * 60 + minutes)
+        }
+    };
+    Utc.from_utc_datetime(&datetime)
+        .checked_sub_signed(Duration::try_minutes(offset_minutes)?)
+}
@@ -394,7 +394,7 @@ impl CompoundType {
     }
 
     /// Size in bytes of this type
-    fn size(&self) -> usize {
+    pub fn size(&self) -> usize {
         let mut size = 0;
         error::checked(super::with_lock(|| unsafe {
             nc_inq_compound(
@@ -662,8 +662,8 @@ impl VariableType {
         }
     }
 
-    /// Id of this type
-    pub(crate) fn id(&self) -> nc_type {
+    /// Id of this type, as used by `netCDF`
+    pub fn id(&self) -> nc_type {
         match self {
             Self::Basic(b) => b.id(),
             Self::String => NC_STRING,
@@ -759,7 +759,7 @@ impl VariableType {
                     NC_OPAQUE => Ok(OpaqueType { ncid, id: xtype }.into()),
                     NC_ENUM => Ok(EnumType { ncid, id: xtype }.into()),
                     NC_COMPOUND => Ok(CompoundType { ncid, id: xtype }.into()),
-                    _ => panic!("Unexpected base type: {base_xtype}"),
+                    _ => Err(error::Error::TypeUnknown(xtype)),
                 }
             }
         }
@@ -50,12 +50,45 @@ impl<'g> std::ops::Deref for VariableMut<'g> {
 pub enum Endianness {
     /// Native endianness, depends on machine architecture (x86_64 is Little)
     Native,
-    /// Lille endian
+    /// Little endian
     Little,
     /// Big endian
     Big,
 }
 
+#[cfg(feature = "ndarray")]
+/// Memory layout of an array, see [`Variable::values_arr_order`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Order {
+    /// The last axis is contiguous (C order), as the data in the file
+    RowMajor,
+    /// The first axis is contiguous (Fortran order)
+    ColumnMajor,
+}
+
+/// Layout of the data of a variable in the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Storage {
+    /// Stored as a single block, the layout of all variables in classic files
+    Contiguous,
+    /// Stored in chunks of the given sizes
+    Chunked(Vec<usize>),
+    /// Small variable stored in the header of the object
+    Compact,
+}
+
+/// Algorithm used for quantization of floating point variables
+#[cfg(feature = "4.9.0")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuantizeMode {
+    /// `BitGroom`, keeping a number of significant decimal digits
+    BitGroom,
+    /// Granular `BitRound`, keeping a number of significant decimal digits
+    GranularBitRound,
+    /// `BitRound`, keeping a number of significant bits
+    BitRound,
+}
+
 #[allow(clippy::len_without_is_empty)]
 impl<'g> Variable<'g> {
     pub(crate) fn find_from_name(ncid: nc_type, name: &str) -> error::Result<Option<Variable<'g>>> {
@@ -115,40 +148,200 @@ impl<'g> Variable<'g> {
 
         String::from_utf8(name).expect("Variable name contained invalid sequence")
     }
-    /// Get an attribute of this variable
+    /// Get an attribute of this variable, such as the
+    /// `units` or `long_name` metadata of the CF conventions
+    ///
+    /// # Panics
+    ///
+    /// netcdf error when looking up the attribute
     pub fn attribute<'a>(&'a self, name: &str) -> Option<Attribute<'a>> {
         // Need to lock when reading the first attribute (per variable)
         Attribute::find_from_name(self.ncid, Some(self.varid), name)
             .expect("Could not retrieve attribute")
     }
     /// Iterator over all the attributes of this variable
+    ///
+    /// # Examples
+    /// Print all the metadata of a variable
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// for attr in var.attributes() {
+    ///     println!("{}: {:?}", attr.name(), attr.value()?);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// netcdf error when listing the attributes
     pub fn attributes(&self) -> impl Iterator<Item = Attribute> {
         // Need to lock when reading the first attribute (per variable)
         crate::attribute::AttributeIterator::new(self.ncid, Some(self.varid))
             .expect("Could not get attributes")
             .map(Result::unwrap)
     }
+    /// Read the names and values of all the attributes of this variable
+    ///
+    /// # Errors
+    ///
+    /// Not a netCDF variable, or an attribute with a type which can not be read
+    pub fn attribute_map(&self) -> error::Result<Vec<(String, AttrValue)>> {
+        crate::attribute::attribute_map(self.ncid, Some(self.varid))
+    }
     /// Dimensions for a variable
     pub fn dimensions(&self) -> &[Dimension] {
         &self.dimensions
     }
+    /// Current lengths of the dimensions of the variable,
+    /// empty for scalar variables
+    pub fn shape(&self) -> Vec<usize> {
+        self.dimensions.iter().map(Dimension::len).collect()
+    }
+    /// Number of records written along the unlimited dimension
+    /// of this variable
+    ///
+    /// This is a single `nc_inq_dimlen` on the cached dimension
+    /// identifier, cheap enough to call for every record in an
+    /// append loop. For variables with several unlimited dimensions
+    /// the first one is used
+    ///
+    /// # Errors
+    ///
+    /// The variable has no unlimited dimension, or `netCDF` error
+    pub fn record_count(&self) -> error::Result<usize> {
+        let dim = self
+            .dimensions
+            .iter()
+            .find(|d| d.is_unlimited())
+            .ok_or_else(|| error::Error::from("variable has no unlimited dimension"))?;
+        let id = dim.identifier();
+        let mut len = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_dimlen(id.ncid, id.dimid, &mut len)
+            }))?;
+        }
+        Ok(len)
+    }
+    /// Whether this is a coordinate variable, a variable with a
+    /// single dimension of the same name as the variable
+    ///
+    /// # Errors
+    ///
+    /// Not a netCDF variable, or netcdf error
+    pub fn is_coordinate(&self) -> error::Result<bool> {
+        let dim = match self.dimensions.as_slice() {
+            [dim] => dim.identifier(),
+            _ => return Ok(false),
+        };
+        let mut varname = [0_u8; NC_MAX_NAME as usize + 1];
+        let mut dimname = [0_u8; NC_MAX_NAME as usize + 1];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_varname(self.ncid, self.varid, varname.as_mut_ptr().cast())
+            }))?;
+            error::checked(super::with_lock(|| {
+                nc_inq_dimname(dim.ncid, dim.dimid, dimname.as_mut_ptr().cast())
+            }))?;
+        }
+        Ok(varname == dimname)
+    }
+    /// Whether this is a record variable, a variable with an
+    /// unlimited dimension
+    ///
+    /// In classic files only the first dimension can be unlimited,
+    /// `netCDF-4` files allows unlimited dimensions in any position
+    ///
+    /// # Errors
+    ///
+    /// Not a netCDF variable, or netcdf error
+    pub fn is_record(&self) -> error::Result<bool> {
+        Ok(self.dimensions.iter().any(Dimension::is_unlimited))
+    }
+    /// Names of the dimensions of the variable, in the same order
+    /// as the dimensions, without querying their lengths
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?;
+    /// let var = file.variable("temp").unwrap();
+    /// // Prints e.g. temp(time, lat, lon)
+    /// println!("{}({})", var.name(), var.dimension_names()?.join(", "));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Not a netCDF variable, or netcdf error
+    pub fn dimension_names(&self) -> error::Result<Vec<String>> {
+        let mut ndims = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_varndims(self.ncid, self.varid, &mut ndims)
+            }))?;
+        }
+        let mut dimids = vec![0; ndims.try_into()?];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_vardimid(self.ncid, self.varid, dimids.as_mut_ptr())
+            }))?;
+        }
+        dimids
+            .into_iter()
+            .map(|dimid| {
+                let mut name = [0_u8; NC_MAX_NAME as usize + 1];
+                unsafe {
+                    error::checked(super::with_lock(|| {
+                        nc_inq_dimname(self.ncid, dimid, name.as_mut_ptr().cast())
+                    }))?;
+                }
+                let len = name.iter().position(|&x| x == 0).unwrap_or(name.len());
+                Ok(String::from_utf8_lossy(&name[..len]).into_owned())
+            })
+            .collect()
+    }
     /// Get the type of this variable
+    ///
+    /// Branch on the returned type to read variables of an unknown
+    /// schema, user defined types carry a handle for further inspection
     pub fn vartype(&self) -> VariableType {
         VariableType::from_id(self.ncid, self.vartype).unwrap()
     }
-    /// Get current length of the variable
+    /// Get current length of the variable, which is the product
+    /// of the lengths of all dimensions (`1` for scalar variables)
+    ///
+    /// Unlimited dimensions contribute their current length, this can
+    /// be used to size a buffer for reading the whole variable
     pub fn len(&self) -> usize {
         self.dimensions
             .iter()
             .map(Dimension::len)
             .fold(1_usize, usize::saturating_mul)
     }
+    /// Whether the variable is in a `netCDF-4` file
+    pub(crate) fn is_netcdf4(&self) -> error::Result<bool> {
+        let mut format = 0;
+        unsafe {
+            error::checked(super::with_lock(|| nc_inq_format(self.ncid, &mut format)))?;
+        }
+        Ok(format == NC_FORMAT_NETCDF4 || format == NC_FORMAT_NETCDF4_CLASSIC)
+    }
+
     /// Get endianness of the variable.
     ///
     /// # Errors
     ///
-    /// Not a `netCDF-4` file
-    pub fn endian_value(&self) -> error::Result<Endianness> {
+    /// Not a `netCDF-4` file ([`Unsupported`](error::Error::Unsupported))
+    pub fn endianness(&self) -> error::Result<Endianness> {
+        if !self.is_netcdf4()? {
+            return Err(error::Error::Unsupported(
+                "endianness is not supported for classic format files",
+            ));
+        }
         let mut e: nc_type = 0;
         unsafe {
             error::checked(super::with_lock(|| {
@@ -162,6 +355,219 @@ impl<'g> Variable<'g> {
             _ => Err(NC_EVARMETA.into()),
         }
     }
+    /// Get endianness of the variable.
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    #[deprecated(note = "renamed to `endianness`")]
+    pub fn endian_value(&self) -> error::Result<Endianness> {
+        self.endianness()
+    }
+
+    /// Get the deflate level and whether shuffling is enabled,
+    /// returns `None` if the variable is not compressed
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    pub fn compression(&self) -> error::Result<Option<(u8, bool)>> {
+        let mut shuffle: nc_type = 0;
+        let mut deflate: nc_type = 0;
+        let mut deflate_level: nc_type = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_var_deflate(
+                    self.ncid,
+                    self.varid,
+                    &mut shuffle,
+                    &mut deflate,
+                    &mut deflate_level,
+                )
+            }))?;
+        }
+        if deflate == 0 {
+            return Ok(None);
+        }
+        Ok(Some((u8::try_from(deflate_level)?, shuffle != 0)))
+    }
+
+    /// Get the chunk sizes of the variable, returns `None` if
+    /// the variable is not chunked (e.g. contiguous storage or scalar)
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    pub fn chunking(&self) -> error::Result<Option<Vec<usize>>> {
+        if self.dimensions.is_empty() {
+            return Ok(None);
+        }
+        let mut storage: nc_type = 0;
+        let mut chunksize = vec![0; self.dimensions.len()];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_var_chunking(self.ncid, self.varid, &mut storage, chunksize.as_mut_ptr())
+            }))?;
+        }
+        if storage == NC_CHUNKED {
+            Ok(Some(chunksize))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the storage layout of the variable
+    ///
+    /// Variables in classic files are always contiguous
+    ///
+    /// # Errors
+    ///
+    /// Unknown storage, or netcdf error
+    pub fn storage(&self) -> error::Result<Storage> {
+        let mut storage: nc_type = 0;
+        let mut chunksize = vec![0; self.dimensions.len()];
+        let chunkptr = if chunksize.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            chunksize.as_mut_ptr()
+        };
+        let e = unsafe {
+            super::with_lock(|| nc_inq_var_chunking(self.ncid, self.varid, &mut storage, chunkptr))
+        };
+        if e == NC_ENOTNC4 {
+            return Ok(Storage::Contiguous);
+        }
+        error::checked(e)?;
+        match storage {
+            NC_CONTIGUOUS => Ok(Storage::Contiguous),
+            NC_CHUNKED => Ok(Storage::Chunked(chunksize)),
+            #[cfg(feature = "4.7.4")]
+            NC_COMPACT => Ok(Storage::Compact),
+            _ => Err(NC_EVARMETA.into()),
+        }
+    }
+
+    /// Get the quantization mode and the number of significant
+    /// digits (or bits for [`QuantizeMode::BitRound`]), returns `None`
+    /// if the variable is not quantized
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    #[cfg(feature = "4.9.0")]
+    pub fn quantization(&self) -> error::Result<Option<(QuantizeMode, u8)>> {
+        let mut mode = 0;
+        let mut nsd = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_var_quantize(self.ncid, self.varid, &mut mode, &mut nsd)
+            }))?;
+        }
+        let mode = match mode {
+            NC_NOQUANTIZE => return Ok(None),
+            NC_QUANTIZE_BITGROOM => QuantizeMode::BitGroom,
+            NC_QUANTIZE_GRANULARBR => QuantizeMode::GranularBitRound,
+            NC_QUANTIZE_BITROUND => QuantizeMode::BitRound,
+            _ => return Err(NC_EVARMETA.into()),
+        };
+        Ok(Some((mode, u8::try_from(nsd)?)))
+    }
+
+    /// Get the `hdf5` filters applied to this variable, as pairs of
+    /// filter id and filter parameters, in the order they are applied
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    #[cfg(feature = "4.8.0")]
+    pub fn filters(&self) -> error::Result<Vec<(u32, Vec<u32>)>> {
+        let mut nfilters = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_var_filter_ids(self.ncid, self.varid, &mut nfilters, std::ptr::null_mut())
+            }))?;
+        }
+        let mut ids = vec![0; nfilters];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_var_filter_ids(
+                    self.ncid,
+                    self.varid,
+                    std::ptr::null_mut(),
+                    ids.as_mut_ptr(),
+                )
+            }))?;
+        }
+
+        ids.into_iter()
+            .map(|id| {
+                let mut nparams = 0;
+                unsafe {
+                    error::checked(super::with_lock(|| {
+                        nc_inq_var_filter_info(
+                            self.ncid,
+                            self.varid,
+                            id,
+                            &mut nparams,
+                            std::ptr::null_mut(),
+                        )
+                    }))?;
+                }
+                let mut params = vec![0; nparams];
+                unsafe {
+                    error::checked(super::with_lock(|| {
+                        nc_inq_var_filter_info(
+                            self.ncid,
+                            self.varid,
+                            id,
+                            std::ptr::null_mut(),
+                            params.as_mut_ptr(),
+                        )
+                    }))?;
+                }
+                Ok((id, params))
+            })
+            .collect()
+    }
+
+    /// Get the chunk cache `(size, nelems, preemption)` of this variable,
+    /// see [`VariableMut::set_chunk_cache`]
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    pub fn chunk_cache(&self) -> error::Result<(usize, usize, f32)> {
+        let mut size = 0;
+        let mut nelems = 0;
+        let mut preemption = 0.0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_get_var_chunk_cache(
+                    self.ncid,
+                    self.varid,
+                    &mut size,
+                    &mut nelems,
+                    &mut preemption,
+                )
+            }))?;
+        }
+        Ok((size, nelems, preemption))
+    }
+
+    /// Whether the fletcher32 checksum filter is enabled
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file
+    pub fn checksum(&self) -> error::Result<bool> {
+        let mut fletcher32: nc_type = 0;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_inq_var_fletcher32(self.ncid, self.varid, &mut fletcher32)
+            }))?;
+        }
+        Ok(fletcher32 != 0)
+    }
 }
 impl<'g> VariableMut<'g> {
     /// Sets compression on the variable. Must be set before filling in data.
@@ -175,8 +581,12 @@ impl<'g> VariableMut<'g> {
     ///
     /// # Errors
     ///
-    /// Not a `netcdf-4` file or `deflate_level` not valid
-    pub fn compression(&mut self, deflate_level: nc_type, shuffle: bool) -> error::Result<()> {
+    /// Not a `netcdf-4` file, data has already been written,
+    /// or `deflate_level` not valid
+    pub fn set_compression(&mut self, deflate_level: u8, shuffle: bool) -> error::Result<()> {
+        if deflate_level > 9 {
+            return Err(format!("invalid deflate level {deflate_level}, must be in 0..=9").into());
+        }
         unsafe {
             error::checked(super::with_lock(|| {
                 nc_def_var_deflate(
@@ -184,7 +594,7 @@ impl<'g> VariableMut<'g> {
                     self.varid,
                     shuffle.into(),
                     <_>::from(true),
-                    deflate_level,
+                    deflate_level.into(),
                 )
             }))?;
         }
@@ -192,6 +602,56 @@ impl<'g> VariableMut<'g> {
         Ok(())
     }
 
+    /// Compress a new variable with the default compression of the file,
+    /// skipping scalars and types with variable length members, which can
+    /// not be compressed
+    pub(crate) fn apply_default_compression(
+        &mut self,
+        compression: Option<(u8, bool)>,
+    ) -> error::Result<()> {
+        let (deflate_level, shuffle) = match compression {
+            Some(compression) => compression,
+            None => return Ok(()),
+        };
+        if self.dimensions.is_empty() || has_variable_length(self.ncid, self.vartype)? {
+            return Ok(());
+        }
+        self.set_compression(deflate_level, shuffle)
+    }
+
+    /// Rename the variable
+    ///
+    /// Files in the classic format which are not in define mode
+    /// will temporarily reenter define mode if this is required
+    ///
+    /// # Errors
+    ///
+    /// Name already in use ([`NameInUse`](error::Error::NameInUse))
+    /// or invalid name
+    pub fn rename(&mut self, new_name: &str) -> error::Result<()> {
+        let cname = super::utils::short_name_to_bytes(new_name)?;
+        let e = super::utils::in_define_mode(self.ncid, || unsafe {
+            nc_rename_var(self.ncid, self.varid, cname.as_ptr().cast())
+        })?;
+        error::checked_name(e, "variable", new_name)
+    }
+
+    /// Enable or disable the fletcher32 checksum filter, which
+    /// detects corruption of the stored data. Must be set before inserting data
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file, late define
+    pub fn set_checksum(&mut self, enable: bool) -> error::Result<()> {
+        let fletcher32 = if enable { NC_FLETCHER32 } else { NC_NOCHECKSUM };
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_def_var_fletcher32(self.ncid, self.varid, fletcher32)
+            }))?;
+        }
+        Ok(())
+    }
+
     /// Set chunking for variable. Must be set before inserting data
     ///
     /// Use this when reading or writing smaller units of the hypercube than
@@ -201,8 +661,10 @@ impl<'g> VariableMut<'g> {
     ///
     /// # Errors
     ///
-    /// Not a `netCDF-4` file or invalid chunksize
-    pub fn chunking(&mut self, chunksize: &[usize]) -> error::Result<()> {
+    /// Not a `netCDF-4` file or invalid chunksize. The chunksize must
+    /// have one element per dimension, and can not exceed the length
+    /// of the dimension (except for unlimited dimensions)
+    pub fn set_chunking(&mut self, chunksize: &[usize]) -> error::Result<()> {
         if self.dimensions.is_empty() {
             // Can't really set chunking, would lead to segfault
             return Ok(());
@@ -210,6 +672,16 @@ impl<'g> VariableMut<'g> {
         if chunksize.len() != self.dimensions.len() {
             return Err(error::Error::SliceLen);
         }
+        for (dim, &size) in self.dimensions.iter().zip(chunksize) {
+            if !dim.is_unlimited() && size > dim.len() {
+                return Err(format!(
+                    "chunk size {size} is larger than the length {} of dimension {}",
+                    dim.len(),
+                    dim.name()
+                )
+                .into());
+            }
+        }
         let len = chunksize
             .iter()
             .copied()
@@ -225,56 +697,169 @@ impl<'g> VariableMut<'g> {
 
         Ok(())
     }
-}
-
-mod sealed {
-    pub trait Sealed {}
-}
-
-#[allow(clippy::doc_markdown)]
-/// This trait allow an implicit cast when fetching
-/// a netCDF variable. These methods are not be called
-/// directly, but used through methods on `Variable`
-///
-/// # Safety
-/// This trait maps directly to netCDF semantics and needs
-/// to upheld invariants therein.
-/// This trait is sealed and can not be implemented for
-/// types outside this crate
-pub trait NcPutGet: sealed::Sealed
-where
-    Self: Sized,
-{
-    /// Constant corresponding to a netcdf type
-    const NCTYPE: nc_type;
 
-    /// Returns a single indexed value of the variable as Self
-    ///
-    /// # Safety
+    /// Quantize the values of a floating point variable, setting the
+    /// bits which are not needed for `nsd` significant digits (or bits
+    /// for [`QuantizeMode::BitRound`]) to improve compression.
+    /// Must be set before inserting data
     ///
-    /// Requires `indices` to be of a valid length
-    unsafe fn get_var1(variable: &Variable, start: &[usize]) -> error::Result<Self>;
-
-    #[allow(clippy::doc_markdown)]
-    /// Put a single value into a netCDF variable
+    /// `nsd` can be up to 7 decimal digits (23 bits) for `f32`
+    /// and 15 decimal digits (52 bits) for `f64` variables
     ///
-    /// # Safety
+    /// # Errors
     ///
-    /// Requires `indices` to be of a valid length
-    unsafe fn put_var1(
-        variable: &mut VariableMut,
-        start: &[usize],
-        value: Self,
-    ) -> error::Result<()>;
+    /// Not a `netCDF-4` file, the variable is not `f32` or `f64`,
+    /// or `nsd` is out of range
+    #[cfg(feature = "4.9.0")]
+    pub fn set_quantization(&mut self, mode: QuantizeMode, nsd: u8) -> error::Result<()> {
+        if self.vartype != NC_FLOAT && self.vartype != NC_DOUBLE {
+            return Err(error::Error::TypeMismatch);
+        }
+        let mode = match mode {
+            QuantizeMode::BitGroom => NC_QUANTIZE_BITGROOM,
+            QuantizeMode::GranularBitRound => NC_QUANTIZE_GRANULARBR,
+            QuantizeMode::BitRound => NC_QUANTIZE_BITROUND,
+        };
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_def_var_quantize(self.ncid, self.varid, mode, nsd.into())
+            }))?;
+        }
+        Ok(())
+    }
 
-    /// Get multiple values at once, without checking the validity of
-    /// `indices` or `slice_len`
+    /// Add the `hdf5` filter `id` with parameters `params` to the variable,
+    /// e.g. `32015` for zstd. Must be set before inserting data
     ///
-    /// # Safety
+    /// Filters which are not part of `hdf5` are loaded as plugins at runtime,
+    /// and must be installed where `hdf5` finds them (see `HDF5_PLUGIN_PATH`),
+    /// both when writing and when reading the variable
     ///
-    /// Requires `values` to be of at least size `slice_len.product()`,
-    /// `indices` and `slice_len` to be of a valid length
-    unsafe fn get_vara(
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file, the filter is not available, or
+    /// the parameters are rejected by the filter
+    #[cfg(feature = "4.6.0")]
+    pub fn set_filter(&mut self, id: u32, params: &[u32]) -> error::Result<()> {
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_def_var_filter(self.ncid, self.varid, id, params.len(), params.as_ptr())
+            }))?;
+        }
+        Ok(())
+    }
+
+    /// Compress the variable using szip. Must be set before inserting data
+    ///
+    /// `options_mask` selects the coding method (`NC_SZIP_NN` or `NC_SZIP_EC`),
+    /// and `pixels_per_block` must be even and at most 32
+    ///
+    /// # Errors
+    ///
+    /// `netCDF` was built without szip, not a `netCDF-4` file,
+    /// or invalid parameters
+    #[cfg(feature = "4.7.4")]
+    pub fn set_szip(&mut self, options_mask: u32, pixels_per_block: u32) -> error::Result<()> {
+        if cfg!(not(feature = "has-szip")) {
+            return Err("szip compression is not available in this build of netCDF".into());
+        }
+        if !(2..=32).contains(&pixels_per_block) || pixels_per_block & 1 != 0 {
+            return Err(format!(
+                "pixels_per_block ({pixels_per_block}) must be even and at most 32"
+            )
+            .into());
+        }
+        let options_mask = options_mask.try_into()?;
+        let pixels_per_block = pixels_per_block.try_into()?;
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_def_var_szip(self.ncid, self.varid, options_mask, pixels_per_block)
+            }))?;
+        }
+        Ok(())
+    }
+
+    /// Set the chunk cache of this variable, for the lifetime of the open file
+    ///
+    /// `size` is the size of the cache in bytes, `nelems` the number of
+    /// chunk slots in the cache, and `preemption` (between `0.0` and `1.0`)
+    /// how eagerly fully read or written chunks are evicted.
+    ///
+    /// For repeated reads of large compressed chunks, the cache should
+    /// hold all chunks touched by a single read, e.g. a `size` of 64 MiB
+    /// with `nelems` as a prime number (such as `1009`) larger than the
+    /// number of chunks fitting in the cache, and a `preemption` of `0.75`.
+    /// Use a `preemption` of `1.0` when every chunk is only read once.
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file or `preemption` outside `0.0..=1.0`
+    pub fn set_chunk_cache(
+        &mut self,
+        size: usize,
+        nelems: usize,
+        preemption: f32,
+    ) -> error::Result<()> {
+        if !(0.0..=1.0).contains(&preemption) {
+            return Err(format!("preemption {preemption} must be between 0.0 and 1.0").into());
+        }
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_set_var_chunk_cache(self.ncid, self.varid, size, nelems, preemption)
+            }))?;
+        }
+        Ok(())
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+#[allow(clippy::doc_markdown)]
+/// This trait allow an implicit cast when fetching
+/// a netCDF variable. These methods are not be called
+/// directly, but used through methods on `Variable`
+///
+/// # Safety
+/// This trait maps directly to netCDF semantics and needs
+/// to upheld invariants therein.
+/// This trait is sealed and can not be implemented for
+/// types outside this crate
+pub trait NcPutGet: sealed::Sealed
+where
+    Self: Sized,
+{
+    /// Constant corresponding to a netcdf type
+    const NCTYPE: nc_type;
+
+    /// Returns a single indexed value of the variable as Self
+    ///
+    /// # Safety
+    ///
+    /// Requires `indices` to be of a valid length
+    unsafe fn get_var1(variable: &Variable, start: &[usize]) -> error::Result<Self>;
+
+    #[allow(clippy::doc_markdown)]
+    /// Put a single value into a netCDF variable
+    ///
+    /// # Safety
+    ///
+    /// Requires `indices` to be of a valid length
+    unsafe fn put_var1(
+        variable: &mut VariableMut,
+        start: &[usize],
+        value: Self,
+    ) -> error::Result<()>;
+
+    /// Get multiple values at once, without checking the validity of
+    /// `indices` or `slice_len`
+    ///
+    /// # Safety
+    ///
+    /// Requires `values` to be of at least size `slice_len.product()`,
+    /// `indices` and `slice_len` to be of a valid length
+    unsafe fn get_vara(
         variable: &Variable,
         start: &[usize],
         count: &[usize],
@@ -389,7 +974,7 @@ macro_rules! impl_numeric {
             // fetch ONE value from variable using `$nc_get_var1`
             unsafe fn get_var1(variable: &Variable, start: &[usize]) -> error::Result<Self> {
                 let mut buff: MaybeUninit<Self> = MaybeUninit::uninit();
-                error::checked(super::with_lock(|| {
+                error::checked(super::with_read_lock(variable.ncid, || {
                     $nc_get_var1_type(
                         variable.ncid,
                         variable.varid,
@@ -422,7 +1007,7 @@ macro_rules! impl_numeric {
                 count: &[usize],
                 values: *mut Self,
             ) -> error::Result<()> {
-                error::checked(super::with_lock(|| {
+                error::checked(super::with_read_lock(variable.ncid, || {
                     $nc_get_vara_type(
                         variable.ncid,
                         variable.varid,
@@ -458,7 +1043,7 @@ macro_rules! impl_numeric {
                 strides: &[isize],
                 values: *mut Self,
             ) -> error::Result<()> {
-                error::checked(super::with_lock(|| {
+                error::checked(super::with_read_lock(variable.ncid, || {
                     $nc_get_vars_type(
                         variable.ncid,
                         variable.varid,
@@ -497,7 +1082,7 @@ macro_rules! impl_numeric {
                 map: &[isize],
                 values: *mut Self,
             ) -> error::Result<()> {
-                error::checked(super::with_lock(|| {
+                error::checked(super::with_read_lock(variable.ncid, || {
                     $nc_get_varm_type(
                         variable.ncid,
                         variable.varid,
@@ -673,6 +1258,95 @@ impl_numeric!(
     nc_put_varm_double,
 );
 
+/// Error returned for all uses of `half::f16`
+#[cfg(feature = "half")]
+const F16_UNSUPPORTED: &str = "netCDF has no half precision type";
+
+#[cfg(feature = "half")]
+impl sealed::Sealed for half::f16 {}
+/// Half precision floats
+///
+/// `netCDF` has no half precision type, and the linked library gives
+/// no access to the native `f16` type of `HDF5`. Creating, reading or
+/// writing `f16` variables fails with [`error::Error::Unsupported`]
+/// instead of storing the values as `f32`
+#[cfg(feature = "half")]
+impl NcPutGet for half::f16 {
+    const NCTYPE: nc_type = NC_NAT;
+
+    unsafe fn get_var1(_variable: &Variable, _start: &[usize]) -> error::Result<Self> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn put_var1(
+        _variable: &mut VariableMut,
+        _start: &[usize],
+        _value: Self,
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn get_vara(
+        _variable: &Variable,
+        _start: &[usize],
+        _count: &[usize],
+        _values: *mut Self,
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn put_vara(
+        _variable: &mut VariableMut,
+        _start: &[usize],
+        _count: &[usize],
+        _values: &[Self],
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn get_vars(
+        _variable: &Variable,
+        _start: &[usize],
+        _count: &[usize],
+        _stride: &[isize],
+        _values: *mut Self,
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn put_vars(
+        _variable: &mut VariableMut,
+        _start: &[usize],
+        _count: &[usize],
+        _stride: &[isize],
+        _values: *const Self,
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn get_varm(
+        _variable: &Variable,
+        _start: &[usize],
+        _count: &[usize],
+        _stride: &[isize],
+        _map: &[isize],
+        _values: *mut Self,
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+
+    unsafe fn put_varm(
+        _variable: &mut VariableMut,
+        _start: &[usize],
+        _count: &[usize],
+        _stride: &[isize],
+        _map: &[isize],
+        _values: *const Self,
+    ) -> error::Result<()> {
+        Err(error::Error::Unsupported(F16_UNSUPPORTED))
+    }
+}
+
 /// Holds the contents of a netcdf string. Use deref to get a `CStr`
 struct NcString {
     data: *mut c_char,
@@ -695,6 +1369,9 @@ impl Drop for NcString {
 impl std::ops::Deref for NcString {
     type Target = CStr;
     fn deref(&self) -> &Self::Target {
+        if self.data.is_null() {
+            return <&CStr>::default();
+        }
         unsafe { CStr::from_ptr(self.data) }
     }
 }
@@ -707,6 +1384,82 @@ impl<'g> VariableMut<'g> {
     {
         Attribute::put(self.ncid, self.varid, name, val.into())
     }
+
+    /// Add an attribute with the exact type of `val`, see [`add_attribute`](Self::add_attribute)
+    pub fn add_attribute_typed(
+        &mut self,
+        name: &str,
+        val: AttrValue,
+    ) -> error::Result<Attribute<'_>> {
+        self.add_attribute(name, val)
+    }
+
+    /// Delete an attribute from this variable
+    ///
+    /// # Errors
+    ///
+    /// `NC_ENOTATT` if the attribute does not exist
+    pub fn del_attribute(&mut self, name: &str) -> error::Result<()> {
+        Attribute::delete(self.ncid, self.varid, name)
+    }
+
+    /// Rename an attribute of this variable, keeping its value
+    ///
+    /// Files in the classic format which are not in define mode
+    /// will temporarily reenter define mode if this is required
+    ///
+    /// # Errors
+    ///
+    /// `NC_ENOTATT` if the attribute does not exist, or `new` is already
+    /// in use ([`NameInUse`](error::Error::NameInUse))
+    pub fn rename_attribute(&mut self, old: &str, new: &str) -> error::Result<()> {
+        Attribute::rename(self.ncid, self.varid, old, new)
+    }
+
+    /// Update the value of an attribute, or add the attribute
+    /// if it does not exist
+    ///
+    /// An existing attribute keeps its type, use
+    /// [`replace_attribute`](Self::replace_attribute) to change the type.
+    /// Files in the classic format which are not in define mode will
+    /// temporarily reenter define mode if the new value is larger
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::append("data.nc")?;
+    /// let mut var = file.variable_mut("distance").unwrap();
+    /// var.set_attribute("units", "km")?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) if the attribute
+    /// exists with another type, or netcdf error
+    pub fn set_attribute<T>(&mut self, name: &str, val: T) -> error::Result<Attribute<'_>>
+    where
+        T: Into<AttrValue>,
+    {
+        Attribute::set(self.ncid, self.varid, name, val.into(), true)
+    }
+
+    /// Write an attribute, overwriting any existing
+    /// attribute `name` whatever its type
+    ///
+    /// Files in the classic format which are not in define mode will
+    /// temporarily reenter define mode if the new value is larger
+    ///
+    /// # Errors
+    ///
+    /// netcdf error, e.g. changing the type of `_FillValue`
+    pub fn replace_attribute<T>(&mut self, name: &str, val: T) -> error::Result<Attribute<'_>>
+    where
+        T: Into<AttrValue>,
+    {
+        Attribute::set(self.ncid, self.varid, name, val.into(), false)
+    }
 }
 
 impl<'g> Variable<'g> {
@@ -727,6 +1480,20 @@ impl<'g> Variable<'g> {
 
     ///  Fetches one specific value at specific indices
     ///  indices must has the same length as self.dimensions.
+    ///
+    /// Scalar (zero-dimensional) variables are read using `()` as indices
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("scalar").unwrap();
+    /// let value = var.value::<f64, _>(())?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`error::Error::DimensionMismatch`] if the indices does not match the
+    /// dimensions of the variable (e.g. `()` for a non-scalar variable)
     pub fn value<T: NcPutGet, E>(&self, indices: E) -> error::Result<T>
     where
         E: TryInto<Extents>,
@@ -736,6 +1503,27 @@ impl<'g> Variable<'g> {
         self.value_mono(&extent)
     }
 
+    /// Fetch a single value at `index`, which must have
+    /// one element for each dimension of the variable
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("grid").unwrap();
+    /// let value = var.value_at::<f32>(&[3, 2])?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`IndexLen`](error::Error::IndexLen) if the index does not match
+    /// the number of dimensions, [`IndexMismatch`](error::Error::IndexMismatch)
+    /// if the index is outside the current length of a dimension
+    pub fn value_at<T: NcPutGet>(&self, index: &[usize]) -> error::Result<T> {
+        check_index(self.dimensions(), index, false)?;
+        unsafe { T::get_var1(self, index) }
+    }
+
     fn string_value_mono(&self, extent: &Extents) -> error::Result<String> {
         let dims = self.dimensions();
         let (start, count, _stride) = extent.get_start_count_stride(dims)?;
@@ -769,6 +1557,100 @@ impl<'g> Variable<'g> {
         self.string_value_mono(&extent)
     }
 
+    fn string_values_mono(&self, extents: &Extents) -> error::Result<Vec<String>> {
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        let mut ptrs: Vec<*mut c_char> = vec![std::ptr::null_mut(); number_of_elements];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_get_vars_string(
+                    self.ncid,
+                    self.varid,
+                    start.as_ptr(),
+                    count.as_ptr(),
+                    stride.as_ptr(),
+                    ptrs.as_mut_ptr(),
+                )
+            }))?;
+        }
+        let strings = ptrs
+            .iter()
+            .map(|&ptr| {
+                if ptr.is_null() {
+                    String::new()
+                } else {
+                    unsafe { CStr::from_ptr(ptr) }
+                        .to_string_lossy()
+                        .into_owned()
+                }
+            })
+            .collect();
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_free_string(number_of_elements, ptrs.as_mut_ptr())
+            }))?;
+        }
+        Ok(strings)
+    }
+
+    /// Reads multiple strings from a string variable
+    pub fn string_values<E>(&self, extents: E) -> error::Result<Vec<String>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents = extents.try_into().map_err(Into::into)?;
+        self.string_values_mono(&extents)
+    }
+
+    /// Reads fixed length strings from a `NC_CHAR` variable, where
+    /// the last dimension is the length of the strings
+    ///
+    /// This is how strings are stored in classic files. A variable with
+    /// dimensions `(n, strlen)` gives `n` strings, each cut at the first
+    /// `NUL` character and with trailing spaces removed
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) if the variable is not
+    /// `NC_CHAR`, the variable is a scalar, or netcdf error
+    pub fn char_strings(&self) -> error::Result<Vec<String>> {
+        let strlen = char_string_len(self)?;
+        let len = self.len();
+        let mut buf = vec![0_u8; len];
+        if len > 0 {
+            let start = vec![0; self.dimensions.len()];
+            let count = self.shape();
+            unsafe {
+                error::checked(super::with_lock(|| {
+                    nc_get_vara_text(
+                        self.ncid,
+                        self.varid,
+                        start.as_ptr(),
+                        count.as_ptr(),
+                        buf.as_mut_ptr().cast(),
+                    )
+                }))?;
+            }
+        }
+        if strlen == 0 {
+            let (_, leading) = self.dimensions.split_last().unwrap();
+            let nstrings = leading.iter().map(Dimension::len).product();
+            return Ok(vec![String::new(); nstrings]);
+        }
+        Ok(buf
+            .chunks(strlen)
+            .map(|chars| {
+                let end = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+                String::from_utf8_lossy(&chars[..end])
+                    .trim_end_matches(' ')
+                    .to_owned()
+            })
+            .collect())
+    }
+
     fn values_mono<T: NcPutGet>(&self, extents: &Extents) -> error::Result<Vec<T>> {
         let dims = self.dimensions();
         let (start, count, stride) = extents.get_start_count_stride(dims)?;
@@ -784,6 +1666,23 @@ impl<'g> Variable<'g> {
     }
 
     /// Get multiple values from a variable
+    ///
+    /// The values are converted by `netCDF` from the type on disk
+    /// to `T`, see [`values_as`](Self::values_as) for the caveats
+    ///
+    /// # Examples
+    /// Read every other element along the first dimension,
+    /// and every third element along the second dimension
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("grid").unwrap();
+    /// let start = [0, 0];
+    /// let count = [50, 20];
+    /// let stride = [2, 3];
+    /// let values = var.values::<f32, _>((&start, &count, &stride))?;
+    /// # Ok(()) }
+    /// ```
     pub fn values<T: NcPutGet, E>(&self, extents: E) -> error::Result<Vec<T>>
     where
         E: TryInto<Extents>,
@@ -793,40 +1692,547 @@ impl<'g> Variable<'g> {
         self.values_mono(&extents)
     }
 
-    #[cfg(feature = "ndarray")]
-    /// Fetches variable
-    fn values_arr_mono<T: NcPutGet>(&self, extents: &Extents) -> error::Result<ArrayD<T>> {
-        let dims = self.dimensions();
-        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+    /// Get all values of the variable, this is the same as `values(..)`
+    ///
+    /// Scalar variables give a single value
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let lat = file.variable("lat").expect("variable not found");
+    /// let lat = lat.values_all::<f32>()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn values_all<T: NcPutGet>(&self) -> error::Result<Vec<T>> {
+        self.values_mono(&Extents::All)
+    }
 
+    /// Get values from a variable, placed in the returned buffer
+    /// according to `imap`
+    ///
+    /// `imap[i]` is the distance in elements in the returned buffer
+    /// between two consecutive values along dimension `i`. Reading
+    /// with an `imap` of `[1, nrows]` gives for instance a transposed
+    /// (column-major) copy of a 2D variable. `start`, `count` and `stride`
+    /// are as for [`values`](Self::values)
+    ///
+    /// `imap` must fill the buffer of `count` elements without gaps
+    /// or overlaps, as for a permutation of the dimensions
+    ///
+    /// # Errors
+    ///
+    /// The arguments do not match the number of dimensions, a stride
+    /// is zero, `imap` does not fill the buffer exactly, or netcdf error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("grid").unwrap(); // dimensions (3, 4)
+    /// let transposed = var.values_mapped::<f32>(&[0, 0], &[3, 4], &[1, 1], &[1, 3])?;
+    /// # Ok(()) }
+    /// ```
+    pub fn values_mapped<T: NcPutGet>(
+        &self,
+        start: &[usize],
+        count: &[usize],
+        stride: &[isize],
+        imap: &[isize],
+    ) -> error::Result<Vec<T>> {
+        check_mapped(self.dimensions().len(), start, count, stride, imap)?;
         let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        // Dims with a single element can not lead to gaps
+        let mut spans = count
+            .iter()
+            .zip(imap)
+            .filter(|(&c, _)| c > 1)
+            .map(|(&c, &m)| (m, c))
+            .collect::<Vec<_>>();
+        spans.sort_unstable();
+        let mut expected = 1_usize;
+        for (m, c) in spans {
+            if usize::try_from(m).ok() != Some(expected) {
+                return Err("imap does not map onto a contiguous buffer".into());
+            }
+            expected *= c;
+        }
+
         let mut values = Vec::with_capacity(number_of_elements);
         unsafe {
-            T::get_vars(self, &start, &count, &stride, values.as_mut_ptr())?;
+            T::get_varm(self, start, count, stride, imap, values.as_mut_ptr())?;
             values.set_len(number_of_elements);
         };
-
-        Ok(ArrayD::from_shape_vec(count, values).unwrap())
+        Ok(values)
     }
 
-    #[cfg(feature = "ndarray")]
-    /// Fetches variable
-    pub fn values_arr<T: NcPutGet, E>(&self, extents: E) -> error::Result<ArrayD<T>>
+    /// Get values of a byte variable (`NC_BYTE` or `NC_UBYTE`)
+    /// as booleans, where any nonzero value is `true`
+    ///
+    /// # Errors
+    ///
+    /// Not a byte variable, or netcdf error
+    pub fn values_bool<E>(&self, extents: E) -> error::Result<Vec<bool>>
     where
         E: TryInto<Extents>,
         E::Error: Into<error::Error>,
     {
         let extents: Extents = extents.try_into().map_err(Into::into)?;
-        self.values_arr_mono(&extents)
+        match self.vartype {
+            NC_BYTE => Ok(self
+                .values_mono::<i8>(&extents)?
+                .into_iter()
+                .map(|x| x != 0)
+                .collect()),
+            NC_UBYTE => Ok(self
+                .values_mono::<u8>(&extents)?
+                .into_iter()
+                .map(|x| x != 0)
+                .collect()),
+            _ => Err(error::Error::TypeMismatch),
+        }
     }
 
-    /// Get the fill value of a variable
-    pub fn fill_value<T: NcPutGet>(&self) -> error::Result<Option<T>> {
-        if T::NCTYPE != self.vartype {
-            return Err(error::Error::TypeMismatch);
-        }
-        let mut location = std::mem::MaybeUninit::uninit();
-        let mut nofill: nc_type = 0;
+    /// Get multiple values from a variable as `T`
+    ///
+    /// If `strict` is `false` the values are converted from the type
+    /// on disk by `netCDF`. Converting to a type of lower precision
+    /// (`f64` to `f32`, `i32` to `f32`) silently loses precision, while
+    /// values which do not fit in `T` give `NC_ERANGE`.
+    /// If `strict` is `true`, `T` must be the type of the variable
+    ///
+    /// # Errors
+    ///
+    /// `strict` is set and `T` is not the type of the variable,
+    /// values out of range for `T`, or netcdf error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("counts").expect("variable not found");
+    /// let counts = var.values_as::<f64, _>(.., false)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn values_as<T: NcPutGet, E>(&self, extents: E, strict: bool) -> error::Result<Vec<T>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        if strict && self.vartype != T::NCTYPE {
+            return Err(error::Error::TypeMismatch);
+        }
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.values_mono(&extents)
+    }
+
+    /// Iterate over all values of the variable without reading
+    /// the full variable into memory
+    ///
+    /// Values are read a number of rows at a time along the first
+    /// (slowest varying) dimension. This defaults to the chunk size
+    /// along this dimension for chunked variables, and a single row
+    /// otherwise. Use [`ValueIterator::with_chunk_rows`] to change
+    /// the number of rows per read.
+    ///
+    /// # Errors
+    ///
+    /// Not able to get the chunking of the variable
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// let mut sum = 0.0;
+    /// for value in var.value_iter::<f64>()?.with_chunk_rows(10) {
+    ///     sum += value?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn value_iter<T: NcPutGet>(&self) -> error::Result<ValueIterator<'_, 'g, T>> {
+        let (rows, total_rows) = match self.dimensions.first() {
+            None => (1, 1),
+            Some(dim) => {
+                let rows = self.chunking()?.map_or(1, |chunksize| chunksize[0]).max(1);
+                (rows, dim.len())
+            }
+        };
+        Ok(ValueIterator {
+            variable: self,
+            rows,
+            next_row: 0,
+            total_rows,
+            buffer: Vec::new().into_iter(),
+        })
+    }
+
+    /// Iterate over the chunks of the variable, reading one chunk at a time
+    ///
+    /// Every item holds the start of the chunk, and the values of the
+    /// chunk in row-major order. Chunks at the end of a dimension which
+    /// does not divide evenly hold fewer values. Processing the data chunk
+    /// by chunk decompresses every chunk once. Variables which are not
+    /// chunked, such as all variables in classic files, are read as a
+    /// single chunk covering the whole variable. Chunks are visited in
+    /// row-major order of their starts
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("large.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// let mut max = f32::MIN;
+    /// for chunk in var.chunk_iter::<f32>()? {
+    ///     let (start, values) = chunk?;
+    ///     max = values.into_iter().fold(max, f32::max);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Not able to get the storage of the variable, or
+    /// netcdf error when reading a chunk
+    pub fn chunk_iter<T: NcPutGet>(
+        &self,
+    ) -> error::Result<impl Iterator<Item = error::Result<(Vec<usize>, Vec<T>)>> + '_> {
+        let lens = self
+            .dimensions
+            .iter()
+            .map(Dimension::len)
+            .collect::<Vec<_>>();
+        let chunksize = match self.storage()? {
+            Storage::Chunked(chunksize) => chunksize.into_iter().map(|c| c.max(1)).collect(),
+            Storage::Contiguous | Storage::Compact => lens.clone(),
+        };
+        let mut next = if lens.contains(&0) {
+            None
+        } else {
+            Some(vec![0; lens.len()])
+        };
+        Ok(std::iter::from_fn(move || {
+            let start = next.take()?;
+            let count = start
+                .iter()
+                .zip(&chunksize)
+                .zip(&lens)
+                .map(|((&s, &c), &len)| c.min(len - s))
+                .collect::<Vec<_>>();
+
+            // Advance to the next chunk, the last dimension fastest
+            let mut following = start.clone();
+            let mut i = following.len();
+            next = loop {
+                if i == 0 {
+                    break None;
+                }
+                i -= 1;
+                following[i] += chunksize[i];
+                if following[i] < lens[i] {
+                    break Some(following);
+                }
+                following[i] = 0;
+            };
+
+            let stride = vec![1; count.len()];
+            let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+            let mut values = Vec::with_capacity(number_of_elements);
+            let read = unsafe { T::get_vars(self, &start, &count, &stride, values.as_mut_ptr()) };
+            Some(read.map(|()| {
+                unsafe { values.set_len(number_of_elements) };
+                (start, values)
+            }))
+        }))
+    }
+
+    /// Numeric attribute converted to `f64` by `netCDF`
+    fn attribute_as_f64s(&self, name: &str) -> error::Result<Option<Vec<f64>>> {
+        let cname = super::utils::short_name_to_bytes(name)?;
+        let mut len = 0;
+        let e = unsafe {
+            super::with_lock(|| {
+                nc_inq_attlen(self.ncid, self.varid, cname.as_ptr().cast(), &mut len)
+            })
+        };
+        if e == NC_ENOTATT {
+            return Ok(None);
+        }
+        error::checked(e)?;
+
+        let mut values = vec![0.0; len];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_get_att_double(
+                    self.ncid,
+                    self.varid,
+                    cname.as_ptr().cast(),
+                    values.as_mut_ptr(),
+                )
+            }))?;
+        }
+        Ok(Some(values))
+    }
+
+    fn values_unpacked_mono(&self, extents: &Extents) -> error::Result<Vec<f64>> {
+        let mut values = self.values_mono::<f64>(extents)?;
+
+        let mut missing = self.attribute_as_f64s("_FillValue")?.unwrap_or_default();
+        missing.extend(self.attribute_as_f64s("missing_value")?.unwrap_or_default());
+        let scale_factor = self
+            .attribute_as_f64s("scale_factor")?
+            .and_then(|x| x.first().copied());
+        let add_offset = self
+            .attribute_as_f64s("add_offset")?
+            .and_then(|x| x.first().copied());
+
+        for v in &mut values {
+            #[allow(clippy::float_cmp)] // Missing values are exact
+            if missing.iter().any(|m| m == v) {
+                *v = f64::NAN;
+                continue;
+            }
+            if let Some(scale_factor) = scale_factor {
+                *v *= scale_factor;
+            }
+            if let Some(add_offset) = add_offset {
+                *v += add_offset;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Numeric attribute of the same type as `T`, without conversion
+    fn attribute_as_exact<T: NcPutGet>(&self, name: &str) -> error::Result<Option<Vec<T>>> {
+        let cname = super::utils::short_name_to_bytes(name)?;
+        let mut xtype = 0;
+        let mut len = 0;
+        let e = unsafe {
+            super::with_lock(|| {
+                nc_inq_att(
+                    self.ncid,
+                    self.varid,
+                    cname.as_ptr().cast(),
+                    &mut xtype,
+                    &mut len,
+                )
+            })
+        };
+        if e == NC_ENOTATT {
+            return Ok(None);
+        }
+        error::checked(e)?;
+        if xtype != T::NCTYPE {
+            return Err(error::Error::TypeMismatch);
+        }
+
+        let mut values = Vec::<T>::with_capacity(len);
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_get_att(
+                    self.ncid,
+                    self.varid,
+                    cname.as_ptr().cast(),
+                    values.as_mut_ptr().cast(),
+                )
+            }))?;
+            values.set_len(len);
+        }
+        Ok(Some(values))
+    }
+
+    fn values_masked_mono<T: NcPutGet + PartialEq>(
+        &self,
+        extents: &Extents,
+    ) -> error::Result<Vec<Option<T>>> {
+        if T::NCTYPE != self.vartype {
+            return Err(error::Error::TypeMismatch);
+        }
+        let mut missing = self
+            .attribute_as_exact::<T>("_FillValue")?
+            .unwrap_or_default();
+        missing.extend(
+            self.attribute_as_exact::<T>("missing_value")?
+                .unwrap_or_default(),
+        );
+
+        let values = self.values_mono::<T>(extents)?;
+        Ok(values
+            .into_iter()
+            .map(|v| if missing.contains(&v) { None } else { Some(v) })
+            .collect())
+    }
+
+    /// Get values with missing elements masked as `None`
+    ///
+    /// Elements equal to the `_FillValue` attribute or to any of the
+    /// values of the `missing_value` attribute are masked. Both attributes
+    /// are used when present, neither takes precedence over the other.
+    /// Unlike [`values_unpacked`](Self::values_unpacked) this works for
+    /// integer variables, and the comparison is exact since `T` must be
+    /// the type of the variable. The default fill value of `netCDF` is
+    /// not masked when there is no `_FillValue` attribute
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?;
+    /// let var = file.variable("counts").unwrap();
+    /// let valid = var.values_masked::<i32, _>(..)?.into_iter().flatten().count();
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) if `T` or the type of the
+    /// attributes does not match the variable, or invalid `extents`
+    pub fn values_masked<T: NcPutGet + PartialEq, E>(
+        &self,
+        extents: E,
+    ) -> error::Result<Vec<Option<T>>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.values_masked_mono(&extents)
+    }
+
+    /// Get values unpacked according to the CF conventions
+    ///
+    /// The packed data is read as `f64`, and elements equal to any of the
+    /// values in the `_FillValue` or `missing_value` attributes are replaced
+    /// by `NaN`. The remaining elements are unpacked as
+    /// `packed * scale_factor + add_offset`, using the first value of the
+    /// `scale_factor` and `add_offset` attributes. A missing attribute
+    /// is skipped in the unpacking, `values_unpacked` is then
+    /// equivalent to `values::<f64, _>` with missing values masked
+    ///
+    /// # Errors
+    ///
+    /// Any of the attributes above are not numeric, or invalid `extents`
+    pub fn values_unpacked<E>(&self, extents: E) -> error::Result<Vec<f64>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.values_unpacked_mono(&extents)
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Fetches variable
+    fn values_arr_mono<T: NcPutGet>(&self, extents: &Extents) -> error::Result<ArrayD<T>> {
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        let mut values = Vec::with_capacity(number_of_elements);
+        unsafe {
+            T::get_vars(self, &start, &count, &stride, values.as_mut_ptr())?;
+            values.set_len(number_of_elements);
+        };
+
+        Ok(ArrayD::from_shape_vec(count, values).unwrap())
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Fetches variable
+    ///
+    /// The resulting array has one axis per dimension of the
+    /// variable, with the lengths given by `extents`
+    pub fn values_arr<T: NcPutGet, E>(&self, extents: E) -> error::Result<ArrayD<T>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.values_arr_mono(&extents)
+    }
+
+    #[cfg(feature = "ndarray")]
+    /// Fetches variable into an array with the memory layout `order`
+    ///
+    /// [`Order::ColumnMajor`] gives an array with the same shape as
+    /// [`values_arr`](Self::values_arr), but with the first axis contiguous
+    /// in memory. The data is placed directly in this layout by
+    /// `nc_get_varm`, so no transposed copy is made. The standard layout
+    /// of the returned array is the transpose, e.g. `arr.t()` is a
+    /// row-major view of the reversed axes
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use netcdf::Order;
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("grid").unwrap();
+    /// let arr = var.values_arr_order::<f64, _>(.., Order::ColumnMajor)?;
+    /// // Pass the column-major buffer on to Fortran code
+    /// let buffer = arr.as_slice_memory_order().unwrap();
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Invalid `extents`, or netcdf error
+    pub fn values_arr_order<T: NcPutGet, E>(
+        &self,
+        extents: E,
+        order: Order,
+    ) -> error::Result<ArrayD<T>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        match order {
+            Order::RowMajor => self.values_arr_mono(&extents),
+            Order::ColumnMajor => self.values_arr_column_major(&extents),
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    fn values_arr_column_major<T: NcPutGet>(&self, extents: &Extents) -> error::Result<ArrayD<T>> {
+        use ndarray::ShapeBuilder;
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        let mut values = Vec::with_capacity(number_of_elements);
+        if number_of_elements != 0 {
+            let mut imap = Vec::with_capacity(count.len());
+            let mut distance = 1_usize;
+            for &c in &count {
+                imap.push(isize::try_from(distance)?);
+                distance *= c;
+            }
+            unsafe {
+                T::get_varm(self, &start, &count, &stride, &imap, values.as_mut_ptr())?;
+                values.set_len(number_of_elements);
+            };
+        }
+
+        Ok(ArrayD::from_shape_vec(ndarray::IxDyn(&count).f(), values).unwrap())
+    }
+
+    /// Get the fill value of a variable, returns `None` if the
+    /// variable has been set to no fill
+    ///
+    /// # Errors
+    ///
+    /// `T` does not match the type of the variable
+    pub fn fill_value<T: NcPutGet>(&self) -> error::Result<Option<T>> {
+        if T::NCTYPE != self.vartype {
+            return Err(error::Error::TypeMismatch);
+        }
+        let mut location = std::mem::MaybeUninit::uninit();
+        let mut nofill: nc_type = 0;
         unsafe {
             error::checked(super::with_lock(|| {
                 nc_inq_var_fill(
@@ -863,6 +2269,27 @@ impl<'g> Variable<'g> {
     }
     /// Fetches variable into slice
     /// buffer must be able to hold all the requested elements
+    ///
+    /// No allocation is done, making this suitable for reusing
+    /// a buffer across reads of the same shape. Values are converted
+    /// by `netCDF` to the type of the buffer.
+    ///
+    /// # Errors
+    ///
+    /// [`error::Error::BufferLen`] if the length of the buffer does not
+    /// match the number of requested elements
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// let mut buffer = vec![0.0_f32; 10 * 20];
+    /// for t in 0..var.dimensions()[0].len() {
+    ///     var.values_to(&mut buffer, (&[t, 0, 0], &[1, 10, 20]))?;
+    /// }
+    /// # Ok(()) }
+    /// ```
     pub fn values_to<T: NcPutGet, E>(&self, buffer: &mut [T], extents: E) -> error::Result<()>
     where
         E: TryInto<Extents>,
@@ -872,6 +2299,54 @@ impl<'g> Variable<'g> {
         self.values_to_mono(buffer, &extents)
     }
 
+    fn read_into_mono<T: NcPutGet>(
+        &self,
+        buffer: &mut Vec<T>,
+        extents: &Extents,
+    ) -> error::Result<()> {
+        buffer.clear();
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        buffer.reserve(number_of_elements);
+        unsafe {
+            T::get_vars(self, &start, &count, &stride, buffer.as_mut_ptr())?;
+            buffer.set_len(number_of_elements);
+        }
+        Ok(())
+    }
+    /// Fetches variable into a `Vec`, which is cleared and resized
+    /// to the number of requested elements
+    ///
+    /// The allocation of `buffer` is reused, and only grows if
+    /// more elements are requested than the capacity of `buffer`.
+    /// On error `buffer` is left empty
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("file.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// let mut buffer = Vec::<f32>::new();
+    /// for t in 0..var.dimensions()[0].len() {
+    ///     var.read_into(&mut buffer, (t, .., ..))?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Invalid `extents`, or netcdf error
+    pub fn read_into<T: NcPutGet, E>(&self, buffer: &mut Vec<T>, extents: E) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.read_into_mono(buffer, &extents)
+    }
+
     fn raw_values_mono(&self, buf: &mut [u8], extents: &Extents) -> error::Result<()> {
         let dims = self.dimensions();
         let (start, count, stride) = extents.get_start_count_stride(dims)?;
@@ -919,156 +2394,848 @@ impl<'g> Variable<'g> {
         E::Error: Into<error::Error>,
     {
         let extents: Extents = extents.try_into().map_err(Into::into)?;
-        self.raw_values_mono(buf, &extents)
+        self.raw_values_mono(buf, &extents)
+    }
+
+    fn raw_bytes_mono(&self, extents: &Extents, order: Endianness) -> error::Result<Vec<u8>> {
+        let typ = self.vartype();
+        let size = match typ {
+            super::types::VariableType::Basic(_) => typ.size(),
+            _ => return Err(error::Error::TypeMismatch),
+        };
+        let (_start, count, _stride) = extents.get_start_count_stride(self.dimensions())?;
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        let mut buf = vec![0_u8; number_of_elements.saturating_mul(size)];
+        self.raw_values_mono(&mut buf, extents)?;
+
+        let swap = match order {
+            Endianness::Native => false,
+            Endianness::Little => cfg!(target_endian = "big"),
+            Endianness::Big => cfg!(target_endian = "little"),
+        };
+        if swap && size > 1 {
+            for elem in buf.chunks_exact_mut(size) {
+                elem.reverse();
+            }
+        }
+        Ok(buf)
+    }
+    /// Get the values of a numeric variable as bytes in the given byte order
+    ///
+    /// The output has `size_of::<T>()` bytes for every element, where
+    /// `T` is the type of the variable, and the bytes of each element
+    /// are ordered by `order`. This is independent of the endianness
+    /// the variable is stored with in the file
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?;
+    /// let var = file.variable("temperature").unwrap();
+    /// let bytes = var.raw_bytes((0, .., ..), netcdf::Endianness::Little)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) for strings and
+    /// user defined types, invalid extents, or netcdf error
+    pub fn raw_bytes<E>(&self, extents: E, order: Endianness) -> error::Result<Vec<u8>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.raw_bytes_mono(&extents, order)
+    }
+
+    fn values_compound_raw_mono(
+        &self,
+        extents: &Extents,
+    ) -> error::Result<(Vec<u8>, super::types::CompoundType)> {
+        let typ = match self.vartype() {
+            VariableType::Compound(typ) => typ,
+            _ => return Err(error::Error::TypeMismatch),
+        };
+        let (_start, count, _stride) = extents.get_start_count_stride(self.dimensions())?;
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+
+        let mut buf = vec![0_u8; number_of_elements.saturating_mul(typ.size())];
+        self.raw_values_mono(&mut buf, extents)?;
+        Ok((buf, typ))
+    }
+    /// Get the values of a compound variable as bytes, together
+    /// with the compound type describing the layout of each element
+    ///
+    /// Element `i` starts at byte `i * typ.size()`, and the position
+    /// of each field within the element is given by
+    /// [`CompoundField::offset`](super::types::CompoundField::offset)
+    ///
+    /// # Note
+    ///
+    /// The same caveats as for [`raw_values`](Self::raw_values) hold
+    /// for compound types containing strings or variable length arrays
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("compound.nc")?;
+    /// let var = file.variable("obs").expect("variable not found");
+    /// let (bytes, typ) = var.values_compound_raw(..)?;
+    /// for field in typ.fields() {
+    ///     let offset = field.offset();
+    ///     println!("{} starts at byte {offset} in every element", field.name());
+    /// }
+    /// let elements = bytes.chunks_exact(typ.size());
+    /// # Ok(()) }
+    /// ```
+    pub fn values_compound_raw<E>(
+        &self,
+        extents: E,
+    ) -> error::Result<(Vec<u8>, super::types::CompoundType)>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.values_compound_raw_mono(&extents)
+    }
+
+    fn opaque_values_mono(&self, extents: &Extents) -> error::Result<Vec<Vec<u8>>> {
+        let size = match self.vartype() {
+            VariableType::Opaque(typ) => typ.size(),
+            _ => return Err(error::Error::TypeMismatch),
+        };
+        let (_start, count, _stride) = extents.get_start_count_stride(self.dimensions())?;
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+
+        let mut buf = vec![0_u8; number_of_elements.saturating_mul(size)];
+        self.raw_values_mono(&mut buf, extents)?;
+        Ok(buf.chunks_exact(size).map(<[u8]>::to_vec).collect())
+    }
+    /// Get the values of an opaque variable, with
+    /// the bytes of each element in a separate `Vec`
+    ///
+    /// # Errors
+    ///
+    /// Variable is not of an opaque type, or netcdf error
+    pub fn opaque_values<E>(&self, extents: E) -> error::Result<Vec<Vec<u8>>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.opaque_values_mono(&extents)
+    }
+
+    fn vlen_mono<T: NcPutGet>(&self, extent: &Extents) -> error::Result<Vec<T>> {
+        let dims = self.dimensions();
+        let (start, count, _stride) = extent.get_start_count_stride(dims)?;
+
+        let number_of_items = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_items != 1 {
+            return Err(error::Error::BufferLen {
+                wanted: 1,
+                actual: number_of_items,
+            });
+        }
+        if let super::types::VariableType::Vlen(v) = self.vartype() {
+            if v.typ().id() != T::NCTYPE {
+                return Err(error::Error::TypeMismatch);
+            }
+        } else {
+            return Err(error::Error::TypeMismatch);
+        };
+
+        let mut vlen: MaybeUninit<nc_vlen_t> = MaybeUninit::uninit();
+
+        error::checked(super::with_lock(|| unsafe {
+            nc_get_vara(
+                self.ncid,
+                self.varid,
+                start.as_ptr(),
+                count.as_ptr(),
+                vlen.as_mut_ptr().cast(),
+            )
+        }))?;
+
+        let mut vlen = unsafe { vlen.assume_init() };
+
+        let mut v = Vec::<T>::with_capacity(vlen.len);
+        if !vlen.p.is_null() {
+            unsafe {
+                std::ptr::copy_nonoverlapping(vlen.p as *const T, v.as_mut_ptr(), vlen.len);
+                v.set_len(vlen.len);
+            }
+        }
+        error::checked(super::with_lock(|| unsafe { nc_free_vlen(&mut vlen) })).unwrap();
+
+        Ok(v)
+    }
+    /// Get a vlen element
+    pub fn vlen<T: NcPutGet, E>(&self, indices: E) -> error::Result<Vec<T>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extent = indices.try_into().map_err(Into::into)?;
+        self.vlen_mono(&extent)
+    }
+
+    fn vlens_mono<T: NcPutGet>(&self, extents: &Extents) -> error::Result<Vec<Vec<T>>> {
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        if let super::types::VariableType::Vlen(v) = self.vartype() {
+            if v.typ().id() != T::NCTYPE {
+                return Err(error::Error::TypeMismatch);
+            }
+        } else {
+            return Err(error::Error::TypeMismatch);
+        };
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        let mut vlens = (0..number_of_elements)
+            .map(|_| nc_vlen_t {
+                len: 0,
+                p: std::ptr::null_mut(),
+            })
+            .collect::<Vec<_>>();
+        error::checked(super::with_lock(|| unsafe {
+            nc_get_vars(
+                self.ncid,
+                self.varid,
+                start.as_ptr(),
+                count.as_ptr(),
+                stride.as_ptr(),
+                vlens.as_mut_ptr().cast(),
+            )
+        }))?;
+
+        let values = vlens
+            .iter()
+            .map(|vlen| {
+                let mut v = Vec::<T>::with_capacity(vlen.len);
+                if !vlen.p.is_null() {
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(vlen.p as *const T, v.as_mut_ptr(), vlen.len);
+                        v.set_len(vlen.len);
+                    }
+                }
+                v
+            })
+            .collect();
+        // Library allocated the inner buffers
+        error::checked(super::with_lock(|| unsafe {
+            nc_free_vlens(number_of_elements, vlens.as_mut_ptr())
+        }))?;
+
+        Ok(values)
+    }
+    /// Get multiple vlen elements, each element is
+    /// returned as a `Vec` of the base type
+    ///
+    /// # Errors
+    ///
+    /// Variable is not a vlen type with base type `T`, or netcdf error
+    pub fn vlens<T: NcPutGet, E>(&self, extents: E) -> error::Result<Vec<Vec<T>>>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents = extents.try_into().map_err(Into::into)?;
+        self.vlens_mono(&extents)
+    }
+
+    /// Look up the name of an enum member from its value, returns `None`
+    /// if no member has this value
+    ///
+    /// # Errors
+    ///
+    /// Variable is not an enum type with base type `T`
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("flags.nc")?;
+    /// let var = file.variable("quality").expect("variable not found");
+    /// let flag = var.value::<u8, _>(0)?;
+    /// if let Some(name) = var.enum_member_name(flag)? {
+    ///     println!("quality: {name}");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn enum_member_name<T: NcPutGet + Into<i64>>(
+        &self,
+        value: T,
+    ) -> error::Result<Option<String>> {
+        match self.vartype() {
+            VariableType::Enum(e) if e.typ().id() == T::NCTYPE => {
+                Ok(e.name_from_value(value.into()))
+            }
+            _ => Err(error::Error::TypeMismatch),
+        }
+    }
+}
+
+impl<'g> VariableMut<'g> {
+    fn put_value_mono<T: NcPutGet>(&mut self, value: T, extents: &Extents) -> error::Result<()> {
+        let dims = self.dimensions();
+        let (start, count, _stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_items = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_items != 1 {
+            return Err(error::Error::BufferLen {
+                wanted: 1,
+                actual: number_of_items,
+            });
+        }
+
+        unsafe { T::put_var1(self, &start, value) }
+    }
+    /// Put a single value at `indices`, use `()` as indices for
+    /// scalar variables
+    pub fn put_value<T: NcPutGet, E>(&mut self, value: T, extents: E) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_value_mono(value, &extents)
+    }
+
+    /// Put a single value at `index`, which must have
+    /// one element for each dimension of the variable
+    ///
+    /// Writing beyond the current length of an unlimited
+    /// dimension grows the dimension
+    ///
+    /// # Errors
+    ///
+    /// [`IndexLen`](error::Error::IndexLen) if the index does not match
+    /// the number of dimensions, [`IndexMismatch`](error::Error::IndexMismatch)
+    /// if the index is outside a fixed size dimension
+    pub fn put_value_at<T: NcPutGet>(&mut self, value: T, index: &[usize]) -> error::Result<()> {
+        check_index(self.dimensions(), index, true)?;
+        unsafe { T::put_var1(self, index, value) }
+    }
+
+    fn put_string_mono(&mut self, value: &str, extent: &Extents) -> error::Result<()> {
+        let dims = self.dimensions();
+        let (start, count, _stride) = extent.get_start_count_stride(dims)?;
+
+        let number_of_items = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_items != 1 {
+            return Err(error::Error::BufferLen {
+                wanted: 1,
+                actual: number_of_items,
+            });
+        }
+
+        let value = std::ffi::CString::new(value)?;
+        let mut ptr = value.as_ptr();
+
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_put_var1_string(self.ncid, self.varid, start.as_ptr(), &mut ptr)
+            }))?;
+        }
+
+        Ok(())
+    }
+    /// Internally converts to a `CString`, avoid using this function when performance
+    /// is important
+    ///
+    /// # Errors
+    ///
+    /// `value` contains interior null bytes, or netcdf error
+    pub fn put_string<E>(&mut self, value: &str, extent: E) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extent.try_into().map_err(Into::into)?;
+        self.put_string_mono(value, &extents)
+    }
+
+    fn put_strings_mono<S: AsRef<str>>(
+        &mut self,
+        values: &[S],
+        extents: &Extents,
+    ) -> error::Result<()> {
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_elements != values.len() {
+            return Err(error::Error::BufferLen {
+                wanted: number_of_elements,
+                actual: values.len(),
+            });
+        }
+
+        let values = values
+            .iter()
+            .map(|s| std::ffi::CString::new(s.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut ptrs = values.iter().map(|s| s.as_ptr()).collect::<Vec<_>>();
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_put_vars_string(
+                    self.ncid,
+                    self.varid,
+                    start.as_ptr(),
+                    count.as_ptr(),
+                    stride.as_ptr(),
+                    ptrs.as_mut_ptr(),
+                )
+            }))?;
+        }
+        Ok(())
+    }
+    /// Put multiple strings into a string variable, the
+    /// strings are converted to `CString`s before writing
+    pub fn put_strings<S: AsRef<str>, E>(&mut self, values: &[S], extents: E) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_strings_mono(values, &extents)
+    }
+
+    /// Put fixed length strings into a `NC_CHAR` variable, where the
+    /// last dimension has length `strlen`
+    ///
+    /// Each string is padded with `NUL` characters or truncated to
+    /// `strlen` bytes, without splitting multi-byte characters. The
+    /// strings fill the variable from the start, and a leading
+    /// unlimited dimension of a `(n, strlen)` variable grows to fit
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create_with("stations.nc", netcdf::Options::CLASSIC)?;
+    /// file.add_dimension("station", 2)?;
+    /// file.add_dimension("strlen", 8)?;
+    /// let char_type = netcdf::types::VariableType::Basic(netcdf::types::BasicType::Char);
+    /// let mut var = file.add_variable_with_type("name", &["station", "strlen"], &char_type)?;
+    /// var.put_char_strings(&["Oslo", "Bergen"], 8)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) if the variable is not
+    /// `NC_CHAR`, the last dimension is not of length `strlen`, the number
+    /// of strings does not match the other dimensions, or netcdf error
+    pub fn put_char_strings<S: AsRef<str>>(
+        &mut self,
+        strings: &[S],
+        strlen: usize,
+    ) -> error::Result<()> {
+        let len = char_string_len(self)?;
+        let dims = self.dimensions();
+        let last = &dims[dims.len() - 1];
+        if len != strlen {
+            return Err(format!(
+                "strings of length {strlen} do not fit dimension {} of length {len}",
+                last.name()
+            )
+            .into());
+        }
+        let mut count = self.shape();
+        if dims.len() == 2 && dims[0].is_unlimited() {
+            count[0] = strings.len();
+        }
+        let wanted = count[..dims.len() - 1].iter().product::<usize>();
+        if wanted != strings.len() {
+            return Err(error::Error::BufferLen {
+                wanted,
+                actual: strings.len(),
+            });
+        }
+
+        let mut buf = vec![0_u8; strings.len() * strlen];
+        if strlen > 0 {
+            for (chars, s) in buf.chunks_mut(strlen).zip(strings) {
+                let s = s.as_ref();
+                let mut end = s.len().min(strlen);
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                chars[..end].copy_from_slice(&s.as_bytes()[..end]);
+            }
+        }
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let start = vec![0; dims.len()];
+        unsafe {
+            error::checked(super::with_lock(|| {
+                nc_put_vara_text(
+                    self.ncid,
+                    self.varid,
+                    start.as_ptr(),
+                    count.as_ptr(),
+                    buf.as_ptr().cast(),
+                )
+            }))
+        }
+    }
+
+    fn put_values_mono<T: NcPutGet>(
+        &mut self,
+        values: &[T],
+        extents: &Extents,
+    ) -> error::Result<()> {
+        let dims = self.dimensions();
+        let (start, mut count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements_to_put = values.len();
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_elements != number_of_elements_to_put {
+            if dims.len() == 1 {
+                count[0] = values.len();
+            } else {
+                return Err(error::Error::BufferLen {
+                    wanted: number_of_elements,
+                    actual: number_of_elements_to_put,
+                });
+            }
+        }
+
+        unsafe {
+            T::put_vars(self, &start, &count, &stride, values.as_ptr())?;
+        };
+        Ok(())
+    }
+    /// Put a slice of values at `indices`
+    pub fn put_values<T: NcPutGet, E>(&mut self, values: &[T], extents: E) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_values_mono(values, &extents)
     }
 
-    fn vlen_mono<T: NcPutGet>(&self, extent: &Extents) -> error::Result<Vec<T>> {
+    /// Put the values of the whole variable, the counterpart of
+    /// [`Variable::values_all`]
+    ///
+    /// `values` must have one element for every element of the
+    /// variable, in row-major order. Scalar variables take a single value
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create("grid.nc")?;
+    /// file.add_dimension("lat", 2)?;
+    /// file.add_dimension("lon", 3)?;
+    /// let mut var = file.add_variable::<f32>("depth", &["lat", "lon"])?;
+    /// var.put_all(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The variable has an unlimited dimension, where the length of
+    /// the whole variable is not defined and
+    /// [`put_values`](Self::put_values) must be used with explicit
+    /// extents, `values` does not match the number of elements, or
+    /// netcdf error
+    pub fn put_all<T: NcPutGet>(&mut self, values: &[T]) -> error::Result<()> {
         let dims = self.dimensions();
-        let (start, count, _stride) = extent.get_start_count_stride(dims)?;
-
-        let number_of_items = count.iter().copied().fold(1_usize, usize::saturating_mul);
-        if number_of_items != 1 {
+        if let Some(dim) = dims.iter().find(|dim| dim.is_unlimited()) {
+            return Err(format!(
+                "dimension {} is unlimited, put values with explicit extents",
+                dim.name()
+            )
+            .into());
+        }
+        let count = dims.iter().map(Dimension::len).collect::<Vec<_>>();
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if values.len() != number_of_elements {
             return Err(error::Error::BufferLen {
-                wanted: 1,
-                actual: number_of_items,
+                wanted: number_of_elements,
+                actual: values.len(),
             });
         }
-        if let super::types::VariableType::Vlen(v) = self.vartype() {
-            if v.typ().id() != T::NCTYPE {
-                return Err(error::Error::TypeMismatch);
-            }
-        } else {
-            return Err(error::Error::TypeMismatch);
+        let start = vec![0; count.len()];
+        let stride = vec![1; count.len()];
+        unsafe {
+            T::put_vars(self, &start, &count, &stride, values.as_ptr())?;
         };
+        Ok(())
+    }
 
-        let mut vlen: MaybeUninit<nc_vlen_t> = MaybeUninit::uninit();
-
-        error::checked(super::with_lock(|| unsafe {
-            nc_get_vara(
-                self.ncid,
-                self.varid,
-                start.as_ptr(),
-                count.as_ptr(),
-                vlen.as_mut_ptr().cast(),
-            )
-        }))?;
-
-        let mut vlen = unsafe { vlen.assume_init() };
-
-        let mut v = Vec::<T>::with_capacity(vlen.len);
+    fn put_values_from_mono<T: NcPutGet>(
+        &mut self,
+        iter: impl Iterator<Item = T>,
+        extents: &Extents,
+    ) -> error::Result<()> {
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
 
-        unsafe {
-            std::ptr::copy_nonoverlapping(vlen.p as *const T, v.as_mut_ptr(), vlen.len);
-            v.set_len(vlen.len);
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        let mut values = Vec::with_capacity(number_of_elements);
+        // One extra element to detect iterators which are too long
+        values.extend(iter.take(number_of_elements.saturating_add(1)));
+        if values.len() != number_of_elements {
+            return Err(error::Error::BufferLen {
+                wanted: number_of_elements,
+                actual: values.len(),
+            });
         }
-        error::checked(super::with_lock(|| unsafe { nc_free_vlen(&mut vlen) })).unwrap();
 
-        Ok(v)
+        unsafe {
+            T::put_vars(self, &start, &count, &stride, values.as_ptr())?;
+        };
+        Ok(())
     }
-    /// Get a vlen element
-    pub fn vlen<T: NcPutGet, E>(&self, indices: E) -> error::Result<Vec<T>>
+    /// Put the values yielded by an iterator at `extents`
+    ///
+    /// The values are gathered into a buffer and written in a single
+    /// call. The iterator must yield exactly as many elements as
+    /// given by `extents`, the length of the unlimited dimensions
+    /// is not inferred as for [`put_values`](Self::put_values)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create("generated.nc")?;
+    /// file.add_dimension("x", 100)?;
+    /// let mut var = file.add_variable::<f64>("sin", &["x"])?;
+    /// var.put_values_from((0..100).map(|i| f64::from(i).sin()), ..)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`BufferLen`](error::Error::BufferLen) if the iterator yields
+    /// too few or too many elements, invalid `extents`, or netcdf error
+    pub fn put_values_from<T: NcPutGet, I, E>(&mut self, iter: I, extents: E) -> error::Result<()>
     where
+        I: IntoIterator<Item = T>,
         E: TryInto<Extents>,
         E::Error: Into<error::Error>,
     {
-        let extent = indices.try_into().map_err(Into::into)?;
-        self.vlen_mono(&extent)
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_values_from_mono(iter.into_iter(), &extents)
     }
-}
-
-impl<'g> VariableMut<'g> {
-    fn put_value_mono<T: NcPutGet>(&mut self, value: T, extents: &Extents) -> error::Result<()> {
-        let dims = self.dimensions();
-        let (start, count, _stride) = extents.get_start_count_stride(dims)?;
-
-        let number_of_items = count.iter().copied().fold(1_usize, usize::saturating_mul);
-        if number_of_items != 1 {
+    /// Put a slice of values at every `stride` element along each
+    /// dimension, starting from `start`, with `count` elements
+    /// along each dimension
+    ///
+    /// # Errors
+    ///
+    /// `start`, `count` and `stride` do not match the number of dimensions,
+    /// a stride is not positive, the length of `values` does not match the
+    /// product of `count`, [`OutOfBounds`](error::Error::OutOfBounds) if
+    /// the elements do not fit in the dimensions, or netcdf error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create("strided.nc")?;
+    /// file.add_dimension("x", 6)?;
+    /// let mut var = file.add_variable::<i32>("v", &["x"])?;
+    /// // Interleave two datasets
+    /// var.put_values_strided(&[1, 2, 3], &[0], &[3], &[2])?;
+    /// var.put_values_strided(&[4, 5, 6], &[1], &[3], &[2])?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put_values_strided<T: NcPutGet>(
+        &mut self,
+        values: &[T],
+        start: &[usize],
+        count: &[usize],
+        stride: &[isize],
+    ) -> error::Result<()> {
+        let ndims = self.dimensions().len();
+        if start.len() != ndims || count.len() != ndims || stride.len() != ndims {
+            return Err(error::Error::SliceLen);
+        }
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_elements != values.len() {
             return Err(error::Error::BufferLen {
-                wanted: 1,
-                actual: number_of_items,
+                wanted: number_of_elements,
+                actual: values.len(),
             });
         }
-
-        unsafe { T::put_var1(self, &start, value) }
+        let extents: Extents = (start, count, stride).try_into()?;
+        self.put_values_mono(values, &extents)
     }
-    /// Put a single value at `indices`
-    pub fn put_value<T: NcPutGet, E>(&mut self, value: T, extents: E) -> error::Result<()>
+    /// Put booleans into a byte variable (`NC_BYTE` or `NC_UBYTE`),
+    /// stored as `1` for `true` and `0` for `false`
+    ///
+    /// # Errors
+    ///
+    /// Not a byte variable, the number of values does
+    /// not match `extents`, or netcdf error
+    pub fn put_values_bool<E>(&mut self, values: &[bool], extents: E) -> error::Result<()>
     where
         E: TryInto<Extents>,
         E::Error: Into<error::Error>,
     {
         let extents: Extents = extents.try_into().map_err(Into::into)?;
-        self.put_value_mono(value, &extents)
+        if self.vartype != NC_BYTE && self.vartype != NC_UBYTE {
+            return Err(error::Error::TypeMismatch);
+        }
+        let values = values.iter().map(|&x| i8::from(x)).collect::<Vec<_>>();
+        self.put_values_mono(&values, &extents)
     }
+    /// Put values taken from `values` according to `imap`,
+    /// the inverse of [`values_mapped`](Variable::values_mapped)
+    ///
+    /// `imap[i]` is the distance in elements in `values` between
+    /// two consecutive values along dimension `i`
+    ///
+    /// # Errors
+    ///
+    /// The arguments do not match the number of dimensions, a stride is
+    /// zero, `imap` is negative, `values` is too short for `imap`, or netcdf error
+    pub fn put_values_mapped<T: NcPutGet>(
+        &mut self,
+        values: &[T],
+        start: &[usize],
+        count: &[usize],
+        stride: &[isize],
+        imap: &[isize],
+    ) -> error::Result<()> {
+        check_mapped(self.dimensions().len(), start, count, stride, imap)?;
+        if count.contains(&0) {
+            return Ok(());
+        }
+        let mut last = 0_usize;
+        for (&c, &m) in count.iter().zip(imap) {
+            if c > 1 {
+                let m = usize::try_from(m).map_err(|_| "imap can not be negative")?;
+                last = (c - 1)
+                    .checked_mul(m)
+                    .and_then(|offset| last.checked_add(offset))
+                    .ok_or(error::Error::Overflow)?;
+            }
+        }
+        if last >= values.len() {
+            return Err(error::Error::BufferLen {
+                wanted: last + 1,
+                actual: values.len(),
+            });
+        }
 
-    fn put_string_mono(&mut self, value: &str, extent: &Extents) -> error::Result<()> {
+        unsafe { T::put_varm(self, start, count, stride, imap, values.as_ptr()) }
+    }
+    /// Append one record to a variable whose first dimension
+    /// is unlimited, growing the dimension by one
+    ///
+    /// # Errors
+    ///
+    /// The first dimension is not unlimited, the length of `values`
+    /// does not match the product of the remaining dimensions, or netcdf error
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::create("records.nc")?;
+    /// file.add_unlimited_dimension("time")?;
+    /// file.add_dimension("x", 2)?;
+    /// let mut var = file.add_variable::<f32>("v", &["time", "x"])?;
+    /// var.append(&[1.0, 2.0])?;
+    /// var.append(&[3.0, 4.0])?;
+    /// assert_eq!(var.dimensions()[0].len(), 2);
+    /// # Ok(()) }
+    /// ```
+    pub fn append<T: NcPutGet>(&mut self, values: &[T]) -> error::Result<()> {
         let dims = self.dimensions();
-        let (start, count, _stride) = extent.get_start_count_stride(dims)?;
-
-        let number_of_items = count.iter().copied().fold(1_usize, usize::saturating_mul);
-        if number_of_items != 1 {
+        let (record, rest) = match dims.split_first() {
+            Some((record, rest)) if record.is_unlimited() => (record, rest),
+            _ => return Err("first dimension of variable is not unlimited".into()),
+        };
+        let record_size = rest.iter().map(Dimension::len).product::<usize>();
+        if record_size != values.len() {
             return Err(error::Error::BufferLen {
-                wanted: 1,
-                actual: number_of_items,
+                wanted: record_size,
+                actual: values.len(),
             });
         }
 
-        let value = std::ffi::CString::new(value).expect("String contained interior 0");
-        let mut ptr = value.as_ptr();
-
-        unsafe {
-            error::checked(super::with_lock(|| {
-                nc_put_var1_string(self.ncid, self.varid, start.as_ptr(), &mut ptr)
-            }))?;
-        }
+        let mut start = vec![0; dims.len()];
+        start[0] = record.len();
+        let mut count = rest.iter().map(Dimension::len).collect::<Vec<_>>();
+        count.insert(0, 1);
+        let stride = vec![1; dims.len()];
 
-        Ok(())
-    }
-    /// Internally converts to a `CString`, avoid using this function when performance
-    /// is important
-    pub fn put_string<E>(&mut self, value: &str, extent: E) -> error::Result<()>
-    where
-        E: TryInto<Extents>,
-        E::Error: Into<error::Error>,
-    {
-        let extents: Extents = extent.try_into().map_err(Into::into)?;
-        self.put_string_mono(value, &extents)
+        unsafe { T::put_vars(self, &start, &count, &stride, values.as_ptr()) }
     }
 
-    fn put_values_mono<T: NcPutGet>(
+    #[cfg(feature = "ndarray")]
+    fn put_arr_mono<T: NcPutGet + Clone, D: ndarray::Dimension>(
         &mut self,
-        values: &[T],
+        arr: ndarray::ArrayView<T, D>,
         extents: &Extents,
     ) -> error::Result<()> {
         let dims = self.dimensions();
-        let (start, mut count, stride) = extents.get_start_count_stride(dims)?;
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
 
-        let number_of_elements_to_put = values.len();
-        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
-        if number_of_elements != number_of_elements_to_put {
-            if dims.len() == 1 {
-                count[0] = values.len();
-            } else {
-                return Err(error::Error::BufferLen {
-                    wanted: number_of_elements,
-                    actual: number_of_elements_to_put,
-                });
+        if count.len() != arr.ndim() {
+            return Err(error::Error::DimensionMismatch {
+                wanted: count.len(),
+                actual: arr.ndim(),
+            });
+        }
+        for (&wanted, &actual) in count.iter().zip(arr.shape()) {
+            if wanted != actual {
+                return Err(error::Error::DimensionMismatch { wanted, actual });
             }
         }
 
+        // Non-contiguous views must be copied into an owned buffer
+        let arr = arr.as_standard_layout();
+        let values = arr.as_slice().unwrap();
         unsafe {
             T::put_vars(self, &start, &count, &stride, values.as_ptr())?;
         };
         Ok(())
     }
-    /// Put a slice of values at `indices`
-    pub fn put_values<T: NcPutGet, E>(&mut self, values: &[T], extents: E) -> error::Result<()>
+
+    #[cfg(feature = "ndarray")]
+    /// Put an array of values at `extents`
+    ///
+    /// The shape of the array must match the shape given by `extents`.
+    /// Views which are not in standard layout are copied into a
+    /// contiguous buffer before writing.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = netcdf::append("file.nc")?;
+    /// let mut var = file.variable_mut("grid").unwrap();
+    /// let arr = ndarray::Array2::<f32>::zeros((4, 5));
+    /// var.put_arr(arr.view(), (..4, ..5))?;
+    /// // Write the transpose to another part of the variable
+    /// var.put_arr(arr.t(), (4..9, ..4))?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put_arr<T: NcPutGet + Clone, D: ndarray::Dimension, E>(
+        &mut self,
+        arr: ndarray::ArrayView<T, D>,
+        extents: E,
+    ) -> error::Result<()>
     where
         E: TryInto<Extents>,
         E::Error: Into<error::Error>,
     {
         let extents: Extents = extents.try_into().map_err(Into::into)?;
-        self.put_values_mono(values, &extents)
+        self.put_arr_mono(arr, &extents)
     }
 
     /// Set a Fill Value
@@ -1122,8 +3289,14 @@ impl<'g> VariableMut<'g> {
     ///
     /// # Errors
     ///
-    /// Not a `netCDF-4` file, late define
-    pub fn endian(&mut self, e: Endianness) -> error::Result<()> {
+    /// Not a `netCDF-4` file ([`Unsupported`](error::Error::Unsupported)),
+    /// late define
+    pub fn set_endianness(&mut self, e: Endianness) -> error::Result<()> {
+        if !self.is_netcdf4()? {
+            return Err(error::Error::Unsupported(
+                "endianness is not supported for classic format files",
+            ));
+        }
         let endianness = match e {
             Endianness::Native => NC_ENDIAN_NATIVE,
             Endianness::Little => NC_ENDIAN_LITTLE,
@@ -1136,6 +3309,15 @@ impl<'g> VariableMut<'g> {
         }
         Ok(())
     }
+    /// Set endianness of the variable. Must be set before inserting data
+    ///
+    /// # Errors
+    ///
+    /// Not a `netCDF-4` file, late define
+    #[deprecated(note = "renamed to `set_endianness`")]
+    pub fn endian(&mut self, e: Endianness) -> error::Result<()> {
+        self.set_endianness(e)
+    }
 
     unsafe fn put_raw_values_mono(&mut self, buf: &[u8], extents: &Extents) -> error::Result<()> {
         let dims = self.dimensions();
@@ -1157,35 +3339,145 @@ impl<'g> VariableMut<'g> {
                 actual: bytes_requested,
             });
         }
-
-        #[allow(unused_unsafe)]
-        error::checked(super::with_lock(|| unsafe {
-            nc_put_vars(
-                self.ncid,
-                self.varid,
-                start.as_ptr(),
-                count.as_ptr(),
-                stride.as_ptr(),
-                buf.as_ptr().cast(),
-            )
-        }))
+
+        #[allow(unused_unsafe)]
+        error::checked(super::with_lock(|| unsafe {
+            nc_put_vars(
+                self.ncid,
+                self.varid,
+                start.as_ptr(),
+                count.as_ptr(),
+                stride.as_ptr(),
+                buf.as_ptr().cast(),
+            )
+        }))
+    }
+    /// Get values of any type as bytes
+    ///
+    /// # Safety
+    ///
+    /// When working with compound types, variable length arrays and
+    /// strings create pointers from the buffer, and tries to copy
+    /// memory from these locations. Compound types which does not
+    /// have these elements will be safe to access, and can treat
+    /// this function as safe
+    pub unsafe fn put_raw_values<E>(&mut self, buf: &[u8], extents: E) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_raw_values_mono(buf, &extents)
+    }
+
+    fn put_raw_bytes_mono(
+        &mut self,
+        bytes: &[u8],
+        extents: &Extents,
+        order: Endianness,
+    ) -> error::Result<()> {
+        let typ = self.vartype();
+        let size = match typ {
+            VariableType::Basic(_) => typ.size(),
+            _ => return Err(error::Error::TypeMismatch),
+        };
+        let swap = match order {
+            Endianness::Native => false,
+            Endianness::Little => cfg!(target_endian = "big"),
+            Endianness::Big => cfg!(target_endian = "little"),
+        };
+        let bytes = if swap && size > 1 {
+            let mut buf = bytes.to_vec();
+            for elem in buf.chunks_exact_mut(size) {
+                elem.reverse();
+            }
+            std::borrow::Cow::Owned(buf)
+        } else {
+            std::borrow::Cow::Borrowed(bytes)
+        };
+        // Safe as numeric types hold no pointers
+        unsafe { self.put_raw_values_mono(&bytes, extents) }
+    }
+    /// Put the values of a numeric variable from bytes in the given byte order
+    ///
+    /// `bytes` must hold `size_of::<T>()` bytes for every element, where
+    /// `T` is the type of the variable, with the bytes of each element
+    /// ordered by `order`. The bytes are swapped to the native byte order
+    /// if needed, independent of the endianness the variable is stored
+    /// with in the file. This is the inverse of [`Variable::raw_bytes`]
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let received = vec![0_u8; 4 * 100];
+    /// let mut file = netcdf::append("data.nc")?;
+    /// let mut var = file.variable_mut("temperature").unwrap();
+    /// // Little endian f32 values received over the network
+    /// var.put_raw_bytes(&received, ..100, netcdf::Endianness::Little)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`TypeMismatch`](error::Error::TypeMismatch) for strings and user
+    /// defined types, `bytes` does not match the number of elements in
+    /// `extents`, or netcdf error
+    pub fn put_raw_bytes<E>(
+        &mut self,
+        bytes: &[u8],
+        extents: E,
+        order: Endianness,
+    ) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents: Extents = extents.try_into().map_err(Into::into)?;
+        self.put_raw_bytes_mono(bytes, &extents, order)
+    }
+
+    fn put_opaque_values_mono<B: AsRef<[u8]>>(
+        &mut self,
+        values: &[B],
+        extents: &Extents,
+    ) -> error::Result<()> {
+        let size = match self.vartype() {
+            VariableType::Opaque(typ) => typ.size(),
+            _ => return Err(error::Error::TypeMismatch),
+        };
+        let mut buf = Vec::with_capacity(values.len().saturating_mul(size));
+        for value in values {
+            let value = value.as_ref();
+            if value.len() != size {
+                return Err(error::Error::BufferLen {
+                    wanted: size,
+                    actual: value.len(),
+                });
+            }
+            buf.extend_from_slice(value);
+        }
+        // Opaque types contain no pointers, any bytes are valid
+        unsafe { self.put_raw_values_mono(&buf, extents) }
     }
-    /// Get values of any type as bytes
+    /// Put values into an opaque variable, each
+    /// element must have the size of the opaque type
     ///
-    /// # Safety
+    /// # Errors
     ///
-    /// When working with compound types, variable length arrays and
-    /// strings create pointers from the buffer, and tries to copy
-    /// memory from these locations. Compound types which does not
-    /// have these elements will be safe to access, and can treat
-    /// this function as safe
-    pub unsafe fn put_raw_values<E>(&mut self, buf: &[u8], extents: E) -> error::Result<()>
+    /// Variable is not of an opaque type, an element has the wrong size,
+    /// the number of elements does not match `extents`, or netcdf error
+    pub fn put_opaque_values<B: AsRef<[u8]>, E>(
+        &mut self,
+        values: &[B],
+        extents: E,
+    ) -> error::Result<()>
     where
         E: TryInto<Extents>,
         E::Error: Into<error::Error>,
     {
         let extents: Extents = extents.try_into().map_err(Into::into)?;
-        self.put_raw_values_mono(buf, &extents)
+        self.put_opaque_values_mono(values, &extents)
     }
 
     fn put_vlen_mono<T: NcPutGet>(&mut self, vec: &[T], extent: &Extents) -> error::Result<()> {
@@ -1233,15 +3525,104 @@ impl<'g> VariableMut<'g> {
         let extent = indices.try_into().map_err(Into::into)?;
         self.put_vlen_mono(vec, &extent)
     }
+
+    fn put_vlens_mono<T: NcPutGet, V: AsRef<[T]>>(
+        &mut self,
+        values: &[V],
+        extents: &Extents,
+    ) -> error::Result<()> {
+        let dims = self.dimensions();
+        let (start, count, stride) = extents.get_start_count_stride(dims)?;
+
+        let number_of_elements = count.iter().copied().fold(1_usize, usize::saturating_mul);
+        if number_of_elements != values.len() {
+            return Err(error::Error::BufferLen {
+                wanted: number_of_elements,
+                actual: values.len(),
+            });
+        }
+
+        if let super::types::VariableType::Vlen(v) = self.vartype() {
+            if v.typ().id() != T::NCTYPE {
+                return Err(error::Error::TypeMismatch);
+            }
+        } else {
+            return Err(error::Error::TypeMismatch);
+        };
+
+        let vlens = values
+            .iter()
+            .map(|v| {
+                let v = v.as_ref();
+                nc_vlen_t {
+                    len: v.len(),
+                    p: v.as_ptr() as *mut _,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        error::checked(super::with_lock(|| unsafe {
+            nc_put_vars(
+                self.ncid,
+                self.varid,
+                start.as_ptr(),
+                count.as_ptr(),
+                stride.as_ptr(),
+                vlens.as_ptr().cast(),
+            )
+        }))
+    }
+    /// Put multiple vlen elements, each of which may have a different length
+    ///
+    /// # Errors
+    ///
+    /// Variable is not a vlen type with base type `T`, the number of
+    /// elements does not match `extents`, or netcdf error
+    pub fn put_vlens<T: NcPutGet, V: AsRef<[T]>, E>(
+        &mut self,
+        values: &[V],
+        extents: E,
+    ) -> error::Result<()>
+    where
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents = extents.try_into().map_err(Into::into)?;
+        self.put_vlens_mono(values, &extents)
+    }
 }
 
 impl<'g> VariableMut<'g> {
+    /// Add a variable of the type `xtype`, after checking that
+    /// the type is known in the group `ncid`
+    pub(crate) fn add_from_type_id(
+        ncid: nc_type,
+        xtype: nc_type,
+        name: &str,
+        dims: &[&str],
+    ) -> error::Result<Self> {
+        let e = unsafe {
+            super::with_lock(|| {
+                nc_inq_type(ncid, xtype, std::ptr::null_mut(), std::ptr::null_mut())
+            })
+        };
+        if e == NC_EBADTYPE {
+            return Err(error::Error::TypeUnknown(xtype));
+        }
+        error::checked(e)?;
+        Self::add_from_str(ncid, xtype, name, dims)
+    }
+
     pub(crate) fn add_from_str(
         ncid: nc_type,
         xtype: nc_type,
         name: &str,
         dims: &[&str],
     ) -> error::Result<Self> {
+        #[cfg(feature = "half")]
+        if xtype == NC_NAT {
+            return Err(error::Error::Unsupported(F16_UNSUPPORTED));
+        }
         let dimensions = dims
             .iter()
             .map(
@@ -1257,16 +3638,20 @@ impl<'g> VariableMut<'g> {
         let mut varid = 0;
         unsafe {
             let dimlen = dimensions.len().try_into()?;
-            error::checked(super::with_lock(|| {
-                nc_def_var(
-                    ncid,
-                    cname.as_ptr().cast(),
-                    xtype,
-                    dimlen,
-                    dimensions.as_ptr(),
-                    &mut varid,
-                )
-            }))?;
+            error::checked_name(
+                super::with_lock(|| {
+                    nc_def_var(
+                        ncid,
+                        cname.as_ptr().cast(),
+                        xtype,
+                        dimlen,
+                        dimensions.as_ptr(),
+                        &mut varid,
+                    )
+                }),
+                "variable",
+                name,
+            )?;
         }
 
         let dimensions = dims
@@ -1291,6 +3676,461 @@ impl<'g> VariableMut<'g> {
     }
 }
 
+#[cfg(feature = "tokio")]
+impl<'g> Variable<'g> {
+    /// Read values on the blocking thread pool of `tokio`, without
+    /// blocking the async runtime
+    ///
+    /// The read itself is not asynchronous, `netCDF` still performs
+    /// blocking `I/O` while holding the global lock, but the runtime
+    /// is free to run other tasks meanwhile. As a `Variable` borrows
+    /// the file, the file must be a [`SharedFile`](crate::SharedFile)
+    /// which is kept alive by the blocking task. The returned future
+    /// must be polled within a `tokio` runtime
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = netcdf::open("data.nc")?.into_shared();
+    /// let var = file.variable("temperature").unwrap();
+    /// let values = var.values_async::<f32, _>(&file, (0, .., ..)).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`WrongDataset`](error::Error::WrongDataset) if the variable is not
+    /// from `file`, invalid extents, or netcdf error
+    pub fn values_async<T, E>(
+        &self,
+        file: &crate::SharedFile,
+        extents: E,
+    ) -> impl std::future::Future<Output = error::Result<Vec<T>>> + Send + 'static
+    where
+        T: NcPutGet + Send + 'static,
+        E: TryInto<Extents>,
+        E::Error: Into<error::Error>,
+    {
+        let extents = extents.try_into().map_err(Into::into);
+        let file = file.clone();
+        // The file is moved into the task together with the variable,
+        // which keeps the file open until the read has finished
+        let var = Variable::<'static> {
+            dimensions: self
+                .dimensions
+                .iter()
+                .map(|d| Dimension {
+                    len: d.len,
+                    id: d.id,
+                    _group: PhantomData,
+                })
+                .collect(),
+            vartype: self.vartype,
+            ncid: self.ncid,
+            varid: self.varid,
+            _group: PhantomData,
+        };
+        async move {
+            let extents = extents?;
+            if var.ncid >> 16 != file.ncid() >> 16 {
+                return Err(error::Error::WrongDataset);
+            }
+            tokio::task::spawn_blocking(move || {
+                let values = var.values_mono::<T>(&extents);
+                drop(file);
+                values
+            })
+            .await
+            .map_err(|e| error::Error::from(format!("blocking read failed: {e}")))?
+        }
+    }
+}
+
+/// A builder for a variable, configuring the storage of the
+/// variable before it is created
+///
+/// Created by [`MutableFile::variable_builder`](crate::MutableFile::variable_builder)
+/// or [`GroupMut::variable_builder`](crate::GroupMut::variable_builder).
+/// The settings are validated in [`build`](Self::build) before the
+/// variable is defined, such that an invalid configuration does not
+/// leave a partially configured variable in the file
+#[must_use]
+#[allow(clippy::module_name_repetitions)]
+pub struct VariableBuilder<'f, T> {
+    ncid: nc_type,
+    name: String,
+    dimensions: Vec<String>,
+    chunking: Option<Vec<usize>>,
+    compression: Option<(u8, bool)>,
+    default_compression: Option<(u8, bool)>,
+    fill_value: Option<T>,
+    _file: PhantomData<&'f mut nc_type>,
+}
+
+impl<'f, T: NcPutGet> VariableBuilder<'f, T> {
+    pub(crate) fn new(ncid: nc_type, name: &str) -> Self {
+        Self {
+            ncid,
+            name: name.to_owned(),
+            dimensions: Vec::new(),
+            chunking: None,
+            compression: None,
+            default_compression: None,
+            fill_value: None,
+            _file: PhantomData,
+        }
+    }
+
+    /// Compression used when [`compression`](Self::compression) is not set
+    pub(crate) fn default_compression(mut self, compression: Option<(u8, bool)>) -> Self {
+        self.default_compression = compression;
+        self
+    }
+
+    /// Names of the dimensions of the variable, a scalar
+    /// variable is created if this is not set
+    pub fn dimensions(mut self, dims: &[&str]) -> Self {
+        self.dimensions = dims.iter().map(|&d| d.to_owned()).collect();
+        self
+    }
+
+    /// Chunk sizes of the variable, see [`VariableMut::set_chunking`]
+    pub fn chunking(mut self, chunksize: &[usize]) -> Self {
+        self.chunking = Some(chunksize.to_owned());
+        self
+    }
+
+    /// Compression of the variable, see [`VariableMut::set_compression`]
+    pub fn compression(mut self, deflate_level: u8, shuffle: bool) -> Self {
+        self.compression = Some((deflate_level, shuffle));
+        self
+    }
+
+    /// Fill value of the variable, see [`VariableMut::set_fill_value`]
+    pub fn fill_value(mut self, fill_value: T) -> Self {
+        self.fill_value = Some(fill_value);
+        self
+    }
+
+    /// Define and configure the variable
+    ///
+    /// Classic files in data mode enter define mode once for all the
+    /// definitions, and are returned to data mode afterwards
+    ///
+    /// # Errors
+    ///
+    /// Missing dimensions, invalid name, chunking which does not match
+    /// the dimensions, invalid deflate level, chunking or compression
+    /// requested for a file which is not `netCDF-4`, or netcdf error
+    pub fn build(self) -> error::Result<VariableMut<'f>> {
+        self.validate()?;
+
+        let ncid = self.ncid;
+        let e = unsafe { super::with_lock(|| nc_redef(ncid)) };
+        let entered_define = match e {
+            NC_NOERR => true,
+            NC_EINDEFINE => false,
+            e => return Err(e.into()),
+        };
+        let var = self.define();
+        if entered_define {
+            let e = unsafe { super::with_lock(|| nc_enddef(ncid)) };
+            let var = var?;
+            error::checked(e)?;
+            Ok(var)
+        } else {
+            var
+        }
+    }
+
+    fn validate(&self) -> error::Result<()> {
+        super::utils::short_name_to_bytes(&self.name)?;
+        if let Some((deflate_level, _)) = self.compression {
+            if deflate_level > 9 {
+                return Err(
+                    format!("invalid deflate level {deflate_level}, must be in 0..=9").into(),
+                );
+            }
+        }
+        let dims = self
+            .dimensions
+            .iter()
+            .map(
+                |dimname| match super::dimension::from_name(self.ncid, dimname) {
+                    Ok(Some(dim)) => Ok(dim),
+                    Ok(None) => Err(error::Error::NotFound(format!("dimensions {dimname}"))),
+                    Err(e) => Err(e),
+                },
+            )
+            .collect::<error::Result<Vec<_>>>()?;
+        if let Some(chunksize) = &self.chunking {
+            if chunksize.len() != dims.len() {
+                return Err(error::Error::SliceLen);
+            }
+            for (dim, &size) in dims.iter().zip(chunksize) {
+                if size == 0 || (!dim.is_unlimited() && size > dim.len()) {
+                    return Err(format!(
+                        "invalid chunk size {size} for dimension {} of length {}",
+                        dim.name(),
+                        dim.len()
+                    )
+                    .into());
+                }
+            }
+        }
+        if self.chunking.is_some() || self.compression.is_some() {
+            let mut format = 0;
+            unsafe {
+                error::checked(super::with_lock(|| nc_inq_format(self.ncid, &mut format)))?;
+            }
+            if format != NC_FORMAT_NETCDF4 && format != NC_FORMAT_NETCDF4_CLASSIC {
+                return Err("chunking and compression require a netCDF-4 file".into());
+            }
+        }
+        Ok(())
+    }
+
+    fn define(self) -> error::Result<VariableMut<'f>> {
+        let dims = self
+            .dimensions
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let mut var = VariableMut::add_from_str(self.ncid, T::NCTYPE, &self.name, &dims)?;
+        if let Some(chunksize) = &self.chunking {
+            var.set_chunking(chunksize)?;
+        }
+        match self.compression {
+            Some((deflate_level, shuffle)) => var.set_compression(deflate_level, shuffle)?,
+            None => var.apply_default_compression(self.default_compression)?,
+        }
+        if let Some(fill_value) = self.fill_value {
+            var.set_fill_value(fill_value)?;
+        }
+        Ok(var)
+    }
+}
+
+/// How the values of a variable are read by [`Variable::read_blocks`]
+enum BlockKind {
+    /// Elements of a fixed number of bytes
+    Fixed(usize),
+    /// Strings which must be freed after reading
+    Strings,
+    /// Variable length arrays of fixed size elements
+    Vlens,
+    /// Types with nested allocations, which can not be freed
+    Skip,
+}
+
+/// Whether values of the type `xtype` hold allocations made by `netCDF`
+fn has_variable_length(ncid: nc_type, xtype: nc_type) -> error::Result<bool> {
+    if xtype == NC_STRING {
+        return Ok(true);
+    }
+    if xtype <= NC_MAX_ATOMIC_TYPE {
+        return Ok(false);
+    }
+    let mut nfields = 0;
+    let mut class = 0;
+    unsafe {
+        error::checked(super::with_lock(|| {
+            nc_inq_user_type(
+                ncid,
+                xtype,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut nfields,
+                &mut class,
+            )
+        }))?;
+    }
+    match class {
+        NC_VLEN => Ok(true),
+        NC_COMPOUND => {
+            for fieldid in 0..nfields {
+                let fieldid = fieldid.try_into()?;
+                let mut fieldtype = 0;
+                unsafe {
+                    error::checked(super::with_lock(|| {
+                        nc_inq_compound_fieldtype(ncid, xtype, fieldid, &mut fieldtype)
+                    }))?;
+                }
+                if has_variable_length(ncid, fieldtype)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+impl<'g> Variable<'g> {
+    /// Read all values of the variable in blocks of at most about
+    /// `block_bytes`, discarding the values. Types with nested variable
+    /// length members are skipped
+    pub(crate) fn read_blocks(&self, block_bytes: usize) -> error::Result<()> {
+        let kind = match self.vartype() {
+            VariableType::String => BlockKind::Strings,
+            VariableType::Vlen(_) => {
+                let mut base = 0;
+                unsafe {
+                    error::checked(super::with_lock(|| {
+                        nc_inq_vlen(
+                            self.ncid,
+                            self.vartype,
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            &mut base,
+                        )
+                    }))?;
+                }
+                if has_variable_length(self.ncid, base)? {
+                    BlockKind::Skip
+                } else {
+                    BlockKind::Vlens
+                }
+            }
+            typ => {
+                if has_variable_length(self.ncid, self.vartype)? {
+                    BlockKind::Skip
+                } else {
+                    BlockKind::Fixed(typ.size())
+                }
+            }
+        };
+        let size = match kind {
+            BlockKind::Fixed(size) => size,
+            BlockKind::Strings => std::mem::size_of::<*mut c_char>(),
+            BlockKind::Vlens => std::mem::size_of::<nc_vlen_t>(),
+            BlockKind::Skip => return Ok(()),
+        };
+
+        let lens = self
+            .dimensions
+            .iter()
+            .map(Dimension::len)
+            .collect::<Vec<_>>();
+        if lens.contains(&0) {
+            return Ok(());
+        }
+        // Fill the block from the innermost dimension outwards
+        let mut remaining = (block_bytes / size.max(1)).max(1);
+        let mut block = vec![1; lens.len()];
+        for (b, &len) in block.iter_mut().zip(&lens).rev() {
+            *b = len.min(remaining);
+            remaining /= *b;
+        }
+        let block_elements = block.iter().product::<usize>();
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut strings: Vec<*mut c_char> = Vec::new();
+        let mut vlens: Vec<nc_vlen_t> = Vec::new();
+        match kind {
+            BlockKind::Fixed(_) => buffer.resize(block_elements * size, 0),
+            BlockKind::Strings => strings.resize(block_elements, std::ptr::null_mut()),
+            BlockKind::Vlens => vlens.resize_with(block_elements, || nc_vlen_t {
+                len: 0,
+                p: std::ptr::null_mut(),
+            }),
+            BlockKind::Skip => (),
+        }
+
+        let mut start = vec![0; lens.len()];
+        loop {
+            let count = start
+                .iter()
+                .zip(&block)
+                .zip(&lens)
+                .map(|((&s, &b), &len)| b.min(len - s))
+                .collect::<Vec<_>>();
+            let elements = count.iter().product::<usize>();
+            let ptr: *mut std::ffi::c_void = match kind {
+                BlockKind::Strings => strings.as_mut_ptr().cast(),
+                BlockKind::Vlens => vlens.as_mut_ptr().cast(),
+                BlockKind::Fixed(_) | BlockKind::Skip => buffer.as_mut_ptr().cast(),
+            };
+            unsafe {
+                error::checked(super::with_lock(|| {
+                    nc_get_vara(self.ncid, self.varid, start.as_ptr(), count.as_ptr(), ptr)
+                }))?;
+                match kind {
+                    BlockKind::Strings => error::checked(super::with_lock(|| {
+                        nc_free_string(elements, strings.as_mut_ptr())
+                    }))?,
+                    BlockKind::Vlens => error::checked(super::with_lock(|| {
+                        nc_free_vlens(elements, vlens.as_mut_ptr())
+                    }))?,
+                    _ => (),
+                }
+            }
+
+            // Advance to the next block, the last dimension fastest
+            let mut i = start.len();
+            loop {
+                if i == 0 {
+                    return Ok(());
+                }
+                i -= 1;
+                start[i] += block[i];
+                if start[i] < lens[i] {
+                    break;
+                }
+                start[i] = 0;
+            }
+        }
+    }
+}
+
+/// Length of the strings of a `NC_CHAR` variable, given by the last dimension
+fn char_string_len(var: &Variable) -> error::Result<usize> {
+    if var.vartype != NC_CHAR {
+        return Err(error::Error::TypeMismatch);
+    }
+    match var.dimensions.last() {
+        Some(dim) => Ok(dim.len()),
+        None => Err("fixed length strings need at least one dimension".into()),
+    }
+}
+
+/// Check that `index` points to an element of the variable,
+/// optionally allowing growth of the unlimited dimensions
+fn check_index(dims: &[Dimension], index: &[usize], grow: bool) -> error::Result<()> {
+    if index.len() != dims.len() {
+        return Err(error::Error::IndexLen);
+    }
+    if dims
+        .iter()
+        .zip(index)
+        .any(|(d, &i)| !(grow && d.is_unlimited()) && i >= d.len())
+    {
+        return Err(error::Error::IndexMismatch);
+    }
+    Ok(())
+}
+
+/// Check the rank and strides of arguments to the `varm` functions
+fn check_mapped(
+    ndims: usize,
+    start: &[usize],
+    count: &[usize],
+    stride: &[isize],
+    imap: &[isize],
+) -> error::Result<()> {
+    if [start.len(), count.len(), stride.len(), imap.len()]
+        .iter()
+        .any(|&len| len != ndims)
+    {
+        return Err(error::Error::SliceLen);
+    }
+    if stride.contains(&0) {
+        return Err(error::Error::Stride);
+    }
+    Ok(())
+}
+
 pub(crate) fn variables_at_ncid<'g>(
     ncid: nc_type,
 ) -> error::Result<impl Iterator<Item = error::Result<Variable<'g>>>> {
@@ -1329,6 +4169,10 @@ pub(crate) fn add_variable_from_identifiers<'g>(
     dims: &[super::dimension::Identifier],
     xtype: nc_type,
 ) -> error::Result<VariableMut<'g>> {
+    #[cfg(feature = "half")]
+    if xtype == NC_NAT {
+        return Err(error::Error::Unsupported(F16_UNSUPPORTED));
+    }
     let cname = super::utils::short_name_to_bytes(name)?;
 
     let dimensions = dims
@@ -1340,17 +4184,8 @@ pub(crate) fn add_variable_from_identifiers<'g>(
             if id.ncid >> 16 != ncid >> 16 {
                 return Err(error::Error::WrongDataset);
             }
-            let mut dimlen = 0;
-            unsafe {
-                error::checked(super::with_lock(|| {
-                    nc_inq_dimlen(id.ncid, id.dimid, &mut dimlen)
-                }))?;
-            }
-            Ok(Dimension {
-                len: core::num::NonZeroUsize::new(dimlen),
-                id,
-                _group: PhantomData,
-            })
+            super::dimension::from_identifier(ncid, id)?
+                .ok_or_else(|| error::Error::NotFound(format!("dimension {}", id.dimid)))
         })
         .collect::<error::Result<Vec<_>>>()?;
     let dims = dims.iter().map(|x| x.dimid).collect::<Vec<_>>();
@@ -1358,16 +4193,20 @@ pub(crate) fn add_variable_from_identifiers<'g>(
     let mut varid = 0;
     unsafe {
         let dimlen = dims.len().try_into()?;
-        error::checked(super::with_lock(|| {
-            nc_def_var(
-                ncid,
-                cname.as_ptr().cast(),
-                xtype,
-                dimlen,
-                dims.as_ptr(),
-                &mut varid,
-            )
-        }))?;
+        error::checked_name(
+            super::with_lock(|| {
+                nc_def_var(
+                    ncid,
+                    cname.as_ptr().cast(),
+                    xtype,
+                    dimlen,
+                    dims.as_ptr(),
+                    &mut varid,
+                )
+            }),
+            "variable",
+            name,
+        )?;
     }
 
     Ok(VariableMut(
@@ -1381,3 +4220,66 @@ pub(crate) fn add_variable_from_identifiers<'g>(
         PhantomData,
     ))
 }
+
+/// Iterator over the values of a variable, reading
+/// a number of rows along the first dimension at a time
+///
+/// Created by [`Variable::value_iter`]
+#[allow(clippy::module_name_repetitions)]
+pub struct ValueIterator<'v, 'g, T> {
+    variable: &'v Variable<'g>,
+    rows: usize,
+    next_row: usize,
+    total_rows: usize,
+    buffer: std::vec::IntoIter<T>,
+}
+
+impl<'v, 'g, T> ValueIterator<'v, 'g, T> {
+    /// Set the number of rows along the first dimension
+    /// which are read at a time
+    #[must_use]
+    pub fn with_chunk_rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+}
+
+impl<'v, 'g, T: NcPutGet> Iterator for ValueIterator<'v, 'g, T> {
+    type Item = error::Result<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.buffer.next() {
+            return Some(Ok(value));
+        }
+        if self.next_row >= self.total_rows {
+            return None;
+        }
+        let extents = if self.variable.dimensions.is_empty() {
+            Extents::All
+        } else {
+            let count = self.rows.min(self.total_rows - self.next_row);
+            let mut extents = vec![super::extent::Extent::SliceCount {
+                start: self.next_row,
+                count,
+                stride: 1,
+            }];
+            extents.extend(
+                self.variable.dimensions[1..]
+                    .iter()
+                    .map(|_| super::extent::Extent::from(..)),
+            );
+            Extents::Extent(extents)
+        };
+        self.next_row += self.rows;
+        match self.variable.values_mono::<T>(&extents) {
+            Ok(values) => {
+                self.buffer = values.into_iter();
+                self.next()
+            }
+            Err(e) => {
+                // Do not attempt any further reads
+                self.next_row = self.total_rows;
+                Some(Err(e))
+            }
+        }
+    }
+}
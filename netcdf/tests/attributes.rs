@@ -246,3 +246,431 @@ fn multi_attributes() {
     }
     assert_eq!(atts, 10);
 }
+
+#[test]
+fn delete_attributes() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("delete_attributes.nc");
+    {
+        let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+        file.add_attribute("keep", 1).unwrap();
+        file.add_attribute("mistake", 2).unwrap();
+        file.add_dimension("x", 1).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+        var.add_attribute("units", "m").unwrap();
+        var.add_attribute("mistake", "m").unwrap();
+    }
+    {
+        // Opened in data mode, deleting requires define mode
+        let mut file = netcdf::append(&path).unwrap();
+        file.del_attribute("mistake").unwrap();
+        assert!(matches!(
+            file.del_attribute("mistake").unwrap_err(),
+            netcdf::error::Error::Netcdf(netcdf_sys::NC_ENOTATT)
+        ));
+        let mut var = file.variable_mut("v").unwrap();
+        var.del_attribute("mistake").unwrap();
+        var.del_attribute("nonexistent").unwrap_err();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert!(file.attribute("mistake").is_none());
+    assert!(file.attribute("keep").is_some());
+    let var = file.variable("v").unwrap();
+    assert!(var.attribute("mistake").is_none());
+    assert!(var.attribute("units").is_some());
+}
+
+#[test]
+fn typed_attributes() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("typed_attributes.nc");
+    let mut file = netcdf::create(path).unwrap();
+    file.add_attribute("scale_factor", 0.5_f64).unwrap();
+    file.add_attribute("valid_range", vec![-10_i32, 10])
+        .unwrap();
+    file.add_attribute("units", "K").unwrap();
+
+    let attr = file.attribute("scale_factor").unwrap();
+    assert_eq!(attr.value_as::<f64>().unwrap(), 0.5);
+    assert_eq!(attr.value_as::<Vec<f64>>().unwrap(), [0.5]);
+    attr.value_as::<f32>().unwrap_err();
+
+    let attr = file.attribute("valid_range").unwrap();
+    assert_eq!(attr.value_as::<Vec<i32>>().unwrap(), [-10, 10]);
+    // Array attributes can not be read as a scalar
+    assert!(matches!(
+        attr.value_as::<i32>().unwrap_err(),
+        netcdf::error::Error::TypeMismatch
+    ));
+
+    let attr = file.attribute("units").unwrap();
+    assert_eq!(attr.value_as::<String>().unwrap(), "K");
+    attr.value_as::<u8>().unwrap_err();
+}
+
+#[test]
+fn array_attributes() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("array_attributes.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_attribute("valid_range", [0.0_f32, 50.0, 100.0])
+            .unwrap();
+        file.add_dimension("x", 1).unwrap();
+        let mut var = file.add_variable::<u8>("flags", &["x"]).unwrap();
+        let flag_values: &[u8] = &[1, 2, 4];
+        var.add_attribute("flag_values", flag_values).unwrap();
+        var.add_attribute("flag_masks", [1, 2, 4]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(
+        file.attribute("valid_range").unwrap().value().unwrap(),
+        AttrValue::Floats(vec![0.0, 50.0, 100.0])
+    );
+    let var = file.variable("flags").unwrap();
+    assert_eq!(
+        var.attribute("flag_values").unwrap().value().unwrap(),
+        AttrValue::Uchars(vec![1, 2, 4])
+    );
+    assert_eq!(
+        var.attribute("flag_masks").unwrap().value().unwrap(),
+        AttrValue::Ints(vec![1, 2, 4])
+    );
+}
+
+#[test]
+fn attribute_len_and_type() {
+    use netcdf::AttrType;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("attribute_len_and_type.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_attribute("scalar", 1.5_f64).unwrap();
+    file.add_attribute("array", vec![1_i16, 2, 3]).unwrap();
+    file.add_attribute("text", "æøå").unwrap();
+    file.add_attribute("strs", vec!["a", "bc"]).unwrap();
+
+    for (name, typ, len) in [
+        ("scalar", AttrType::Double, 1),
+        ("array", AttrType::Short, 3),
+        // Number of bytes in the utf-8 encoded string
+        ("text", AttrType::Char, 6),
+        ("strs", AttrType::String, 2),
+    ] {
+        let attr = file.attribute(name).unwrap();
+        assert_eq!(attr.attr_type().unwrap(), typ);
+        assert_eq!(attr.len().unwrap(), len);
+        assert!(!attr.is_empty().unwrap());
+    }
+}
+
+#[test]
+fn text_attributes() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("text_attributes.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_attribute("char", "char array").unwrap();
+    file.add_attribute("string", vec!["string"]).unwrap();
+    file.add_attribute("strings", vec!["a", "b"]).unwrap();
+    file.add_attribute("number", 1_i32).unwrap();
+
+    let text = |name| file.attribute(name).unwrap().text();
+    assert_eq!(text("char").unwrap(), "char array");
+    assert_eq!(text("string").unwrap(), "string");
+    text("strings").unwrap_err();
+    assert!(matches!(
+        text("number").unwrap_err(),
+        netcdf::error::Error::TypeMismatch
+    ));
+
+    let path = d.path().join("text_attributes_classic.nc");
+    let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+    file.add_attribute("title", "classic").unwrap();
+    assert_eq!(file.attribute("title").unwrap().text().unwrap(), "classic");
+}
+
+#[test]
+fn variable_attributes() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("variable_attributes.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_attribute("title", "global").unwrap();
+        file.add_dimension("x", 1).unwrap();
+        let mut var = file.add_variable::<f32>("t", &["x"]).unwrap();
+        var.add_attribute("units", "K").unwrap();
+        var.add_attribute("long_name", "temperature").unwrap();
+        var.add_attribute("standard_name", "air_temperature")
+            .unwrap();
+        file.add_variable::<f32>("empty", &["x"]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("t").unwrap();
+    let names = var
+        .attributes()
+        .map(|a| a.name().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["units", "long_name", "standard_name"]);
+    assert_eq!(var.attribute("units").unwrap().text().unwrap(), "K");
+    assert!(var.attribute("title").is_none());
+
+    let var = file.variable("empty").unwrap();
+    assert_eq!(var.attributes().count(), 0);
+}
+
+#[test]
+fn attribute_map() {
+    use netcdf::AttrValue;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("attribute_map.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_attribute("title", "map").unwrap();
+        file.add_attribute("version", 2).unwrap();
+        file.add_attribute("range", vec![0.5f64, 1.5]).unwrap();
+        file.add_attribute("tags", vec!["a".to_owned(), "b".to_owned()])
+            .unwrap();
+        file.add_dimension("x", 1).unwrap();
+        let mut var = file.add_variable::<f32>("t", &["x"]).unwrap();
+        var.add_attribute("units", "K").unwrap();
+        var.add_attribute("valid", vec![1i16, 2, 3]).unwrap();
+        file.add_variable::<f32>("empty", &["x"]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(
+        file.attribute_map().unwrap(),
+        [
+            ("title".to_owned(), AttrValue::Str("map".into())),
+            ("version".to_owned(), AttrValue::Int(2)),
+            ("range".to_owned(), AttrValue::Doubles(vec![0.5, 1.5])),
+            (
+                "tags".to_owned(),
+                AttrValue::Strs(vec!["a".into(), "b".into()])
+            ),
+        ]
+    );
+    assert_eq!(
+        file.variable("t").unwrap().attribute_map().unwrap(),
+        [
+            ("units".to_owned(), AttrValue::Str("K".into())),
+            ("valid".to_owned(), AttrValue::Shorts(vec![1, 2, 3])),
+        ]
+    );
+    assert!(file
+        .variable("empty")
+        .unwrap()
+        .attribute_map()
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        file.root().unwrap().attribute_map().unwrap().len(),
+        file.attributes().count()
+    );
+}
+
+#[test]
+fn attribute_conversions() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("attribute_conversions.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_attribute("int", 42_i32).unwrap();
+    file.add_attribute("big", u64::MAX).unwrap();
+    file.add_attribute("shorts", vec![-1_i16, 2]).unwrap();
+    file.add_attribute("float", 1.5_f32).unwrap();
+    file.add_attribute("units", "m s-1").unwrap();
+
+    let attr = |name| file.attribute(name).unwrap();
+    assert_eq!(attr("int").value_as::<f64>().unwrap(), 42.0);
+    assert_eq!(attr("int").value_as::<u8>().unwrap(), 42);
+    assert_eq!(attr("int").value_as::<Vec<i64>>().unwrap(), [42]);
+    assert!(matches!(
+        attr("int").value_as::<f32>().unwrap_err(),
+        Error::TypeMismatch
+    ));
+    assert!(matches!(
+        attr("big").value_as::<i64>().unwrap_err(),
+        Error::Conversion(_)
+    ));
+    assert!(matches!(
+        attr("big").value_as::<f64>().unwrap_err(),
+        Error::TypeMismatch
+    ));
+    assert_eq!(attr("shorts").value_as::<Vec<f32>>().unwrap(), [-1.0, 2.0]);
+    assert_eq!(attr("shorts").value_as::<Vec<i8>>().unwrap(), [-1, 2]);
+    attr("shorts").value_as::<Vec<u8>>().unwrap_err();
+    assert_eq!(attr("float").value_as::<f64>().unwrap(), 1.5);
+    attr("float").value_as::<i32>().unwrap_err();
+    let units: String = attr("units").value().unwrap().try_into().unwrap();
+    assert_eq!(units, "m s-1");
+    attr("units").value_as::<f64>().unwrap_err();
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_roundtrip() {
+    use netcdf::AttrType;
+    let values = vec![
+        AttrValue::Uchar(1),
+        AttrValue::Uchars(vec![1, 2]),
+        AttrValue::Schar(-1),
+        AttrValue::Schars(vec![-1, 2]),
+        AttrValue::Ushort(1),
+        AttrValue::Ushorts(vec![1, 2]),
+        AttrValue::Short(-1),
+        AttrValue::Shorts(vec![-1, 2]),
+        AttrValue::Uint(1),
+        AttrValue::Uints(vec![1, 2]),
+        AttrValue::Int(-1),
+        AttrValue::Ints(vec![-1, 2]),
+        AttrValue::Ulonglong(u64::MAX),
+        AttrValue::Ulonglongs(vec![1, u64::MAX]),
+        AttrValue::Longlong(i64::MIN),
+        AttrValue::Longlongs(vec![i64::MIN, 2]),
+        AttrValue::Float(1.5),
+        AttrValue::Floats(vec![1.5, -2.0]),
+        AttrValue::Double(0.1),
+        AttrValue::Doubles(vec![0.1, 1e300]),
+        AttrValue::Str("units".into()),
+        AttrValue::Strs(vec!["a".into(), "b".into()]),
+    ];
+    let json = serde_json::to_string(&values).unwrap();
+    let back: Vec<AttrValue> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, values);
+    assert_eq!(
+        serde_json::to_string(&AttrValue::Short(3)).unwrap(),
+        r#"{"Short":3}"#
+    );
+
+    let types = vec![AttrType::Short, AttrType::Char, AttrType::UserDefined(32)];
+    let json = serde_json::to_string(&types).unwrap();
+    let back: Vec<AttrType> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, types);
+}
+
+#[test]
+fn rename_attribute() {
+    use netcdf::AttrValue;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("rename_attribute.nc");
+    {
+        let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+        file.add_attribute("conv", "CF-1.8").unwrap();
+        file.add_dimension("x", 2).unwrap();
+        let mut var = file.add_variable::<f32>("v", &["x"]).unwrap();
+        var.add_attribute("missing_value", -9999.0_f32).unwrap();
+        var.add_attribute("units", "K").unwrap();
+        file.enddef().unwrap();
+
+        // Longer names require define mode for classic files
+        let mut var = file.variable_mut("v").unwrap();
+        var.rename_attribute("missing_value", "_FillValue_new")
+            .unwrap();
+        var.rename_attribute("_FillValue_new", "fill").unwrap();
+        let err = var.rename_attribute("missing", "other").unwrap_err();
+        assert_eq!(err.code(), Some(netcdf_sys::NC_ENOTATT));
+        let err = var.rename_attribute("fill", "units").unwrap_err();
+        assert_eq!(err.code(), Some(netcdf_sys::NC_ENAMEINUSE));
+        file.rename_attribute("conv", "Conventions").unwrap();
+        file.rename_attribute("conv", "Conventions").unwrap_err();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert!(file.attribute("conv").is_none());
+    assert_eq!(
+        file.attribute("Conventions").unwrap().value().unwrap(),
+        AttrValue::Str("CF-1.8".into())
+    );
+    let var = file.variable("v").unwrap();
+    assert!(var.attribute("missing_value").is_none());
+    assert_eq!(
+        var.attribute("fill").unwrap().value().unwrap(),
+        AttrValue::Float(-9999.0)
+    );
+    assert_eq!(var.attribute("units").unwrap().text().unwrap(), "K");
+}
+
+#[test]
+fn set_attribute() {
+    use netcdf::AttrValue;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("set_attribute.nc");
+    {
+        let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        let mut var = file.add_variable::<f32>("distance", &["x"]).unwrap();
+        var.add_attribute("units", "m").unwrap();
+        var.add_attribute("scale", 1_i32).unwrap();
+        file.enddef().unwrap();
+
+        // Growing an attribute requires define mode for classic files
+        let mut var = file.variable_mut("distance").unwrap();
+        var.set_attribute("units", "km").unwrap();
+        var.set_attribute("comment", "measured").unwrap();
+        let err = var.set_attribute("scale", 0.001_f64).unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+        var.replace_attribute("scale", 0.001_f64).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("distance").unwrap();
+    assert_eq!(var.attribute("units").unwrap().text().unwrap(), "km");
+    assert_eq!(
+        var.attribute("comment").unwrap().text().unwrap(),
+        "measured"
+    );
+    assert_eq!(
+        var.attribute("scale").unwrap().value().unwrap(),
+        AttrValue::Double(0.001)
+    );
+}
+
+#[test]
+fn add_attribute_typed_keeps_narrow_types() {
+    use netcdf::{AttrType, AttrValue};
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("add_attribute_typed.nc");
+    let typed = [
+        ("byte", AttrValue::Schar(-1), AttrType::Schar),
+        ("ubyte", AttrValue::Uchars(vec![1, 2]), AttrType::Uchar),
+        ("short", AttrValue::Short(-2), AttrType::Short),
+        ("ushort", AttrValue::Ushorts(vec![3, 4]), AttrType::Ushort),
+        ("int", AttrValue::Int(4), AttrType::Int),
+        ("float", AttrValue::Float(0.5), AttrType::Float),
+        ("char", AttrValue::Str("m".into()), AttrType::Char),
+    ];
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        for (name, value, typ) in &typed {
+            assert_eq!(value.attr_type(), *typ);
+            file.add_attribute_typed(name, value.clone()).unwrap();
+        }
+        let mut group = file.add_group("g").unwrap();
+        group
+            .add_attribute_typed("short", AttrValue::Shorts(vec![1, 2]))
+            .unwrap();
+        file.add_dimension("x", 1).unwrap();
+        let mut var = file.add_variable::<i16>("v", &["x"]).unwrap();
+        var.add_attribute_typed("valid_range", AttrValue::Shorts(vec![0, 100]))
+            .unwrap();
+        // The conversions keep the width of the Rust type
+        var.add_attribute("scale", 2_i16).unwrap();
+        var.add_attribute("flags", vec![0_u8, 1]).unwrap();
+        var.add_attribute("offset", -1_i8).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    for (name, value, typ) in &typed {
+        let attr = file.attribute(name).unwrap();
+        assert_eq!(attr.attr_type().unwrap(), *typ);
+        assert_eq!(&attr.value().unwrap(), value);
+    }
+    let group = file.group("g").unwrap().unwrap();
+    let attr = group.attribute("short").unwrap();
+    assert_eq!(attr.attr_type().unwrap(), AttrType::Short);
+    let var = file.variable("v").unwrap();
+    for (name, typ) in [
+        ("valid_range", AttrType::Short),
+        ("scale", AttrType::Short),
+        ("flags", AttrType::Uchar),
+        ("offset", AttrType::Schar),
+    ] {
+        assert_eq!(var.attribute(name).unwrap().attr_type().unwrap(), typ);
+    }
+}
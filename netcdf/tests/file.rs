@@ -20,6 +20,34 @@ fn create_with_options() {
     file.add_group("grp").unwrap_err();
 }
 
+#[test]
+fn create_with_format() {
+    use netcdf::Format;
+    let d = tempfile::tempdir().unwrap();
+
+    for (i, format) in [
+        Format::Classic,
+        Format::Offset64,
+        Format::Cdf5,
+        Format::Netcdf4Classic,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let path = d.path().join(format!("create_format{i}.nc"));
+        let mut file = netcdf::create_with(path, format.into()).unwrap();
+        file.add_dimension("t", 1).unwrap();
+        // Classic data model does not support groups or user defined types
+        file.add_group("grp").unwrap_err();
+        file.add_vlen_type::<i32>("vlen").unwrap_err();
+    }
+
+    let path = d.path().join("create_format_nc4.nc");
+    let mut file = netcdf::create_with(path, Format::Netcdf4.into()).unwrap();
+    file.add_group("grp").unwrap();
+    file.add_vlen_type::<i32>("vlen").unwrap();
+}
+
 #[test]
 fn noclobber() {
     let d = tempfile::tempdir().unwrap();
@@ -42,3 +70,551 @@ fn appending_with() {
     let _file =
         netcdf::append_with(&path, netcdf::Options::NETCDF4 | netcdf::Options::DISKLESS).unwrap();
 }
+
+#[test]
+fn sync_before_close() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("sync.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    let mut var = file.add_variable::<i32>("v", &["time"]).unwrap();
+    var.put_values(&[1, 2, 3], [..3]).unwrap();
+    file.sync().unwrap();
+
+    let reader = netcdf::open(&path).unwrap();
+    let var = reader.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2, 3]);
+    drop(reader);
+
+    let mut var = file.variable_mut("v").unwrap();
+    var.put_values(&[4, 5], [3..]).unwrap();
+    file.sync().unwrap();
+
+    let reader = netcdf::open(&path).unwrap();
+    let var = reader.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+#[cfg(unix)]
+fn non_utf8_path() {
+    use std::os::unix::ffi::OsStrExt;
+    let d = tempfile::tempdir().unwrap();
+    let name = std::ffi::OsStr::from_bytes(b"non_utf8_\xff\xfe.nc");
+    let path = d.path().join(name);
+    assert!(path.to_str().is_none());
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 1).unwrap();
+        assert_eq!(file.path().unwrap(), path);
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(file.path().unwrap(), path);
+    assert!(file.dimension("x").is_some());
+    drop(file);
+    netcdf::append(&path).unwrap();
+}
+
+#[test]
+fn path_with_nul_byte() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("nul\0byte.nc");
+
+    let err = netcdf::create(&path).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::InvalidPath(p) if p == path));
+    let err = netcdf::open(&path).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::InvalidPath(_)));
+    let err = netcdf::append(&path).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::InvalidPath(_)));
+}
+
+#[test]
+#[cfg(not(feature = "has-dap"))]
+fn open_dap_unsupported() {
+    let err = netcdf::open_dap("https://example.com/dataset.nc").unwrap_err();
+    assert!(err.to_string().contains("DAP"));
+}
+
+#[test]
+fn query_format() {
+    use netcdf::Format;
+    let d = tempfile::tempdir().unwrap();
+
+    for (i, format) in [
+        Format::Classic,
+        Format::Offset64,
+        Format::Cdf5,
+        Format::Netcdf4,
+        Format::Netcdf4Classic,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let path = d.path().join(format!("query_format{i}.nc"));
+        let file = netcdf::create_with(&path, format.into()).unwrap();
+        assert_eq!(file.format().unwrap(), format);
+        drop(file);
+
+        let file = netcdf::open(&path).unwrap();
+        assert_eq!(file.format().unwrap(), format);
+        assert_eq!(
+            file.root().is_some(),
+            format == Format::Netcdf4 || format == Format::Netcdf4Classic
+        );
+    }
+}
+
+#[test]
+fn define_scope() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("define_scope.nc");
+
+    let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+    let len = file
+        .define(|def| {
+            def.dimension("x", 3)?;
+            def.unlimited_dimension("time")?;
+            def.variable::<i32>("v", &["time", "x"])?;
+            def.attribute("title", "define")?;
+            Ok(3)
+        })
+        .unwrap();
+    assert_eq!(len, 3);
+    // Data mode, values can be written to the classic file
+    let mut var = file.variable_mut("v").unwrap();
+    var.put_values(&[1, 2, 3], (0, ..)).unwrap();
+
+    // Definitions are possible after reentering define mode
+    file.define(|def| def.dimension("y", 2).map(|_| ()))
+        .unwrap();
+    // Still in data mode after an error
+    let err = file
+        .define(|def| def.variable::<i32>("w", &["z"]).map(|_| ()))
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::NotFound(_)));
+    let mut var = file.variable_mut("v").unwrap();
+    var.put_values(&[4, 5, 6], (1, ..)).unwrap();
+    drop(file);
+
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(file.dimension("y").unwrap().len(), 2);
+    assert!(file.variable("w").is_none());
+    let var = file.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn redef_enddef() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("redef_enddef.nc");
+
+    let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.add_variable::<i32>("v", &["x"]).unwrap();
+    file.enddef().unwrap();
+    // Already in data mode
+    file.enddef().unwrap();
+    file.variable_mut("v")
+        .unwrap()
+        .put_values(&[1, 2], ..)
+        .unwrap();
+
+    file.add_dimension("y", 3).unwrap_err();
+    file.redef().unwrap();
+    file.redef().unwrap_err();
+    file.add_dimension("y", 3).unwrap();
+    file.add_variable::<i32>("w", &["y"]).unwrap();
+    file.enddef().unwrap();
+    file.variable_mut("w")
+        .unwrap()
+        .put_values(&[3, 4, 5], ..)
+        .unwrap();
+    drop(file);
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2]);
+    let var = file.variable("w").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [3, 4, 5]);
+}
+
+#[test]
+fn fill_mode() {
+    let d = tempfile::tempdir().unwrap();
+    let values = (0..1000).collect::<Vec<i32>>();
+
+    for (i, options) in [netcdf::Options::CLASSIC, netcdf::Options::NETCDF4]
+        .into_iter()
+        .enumerate()
+    {
+        let path = d.path().join(format!("fill_mode{i}.nc"));
+        {
+            let mut file = netcdf::create_with(&path, options).unwrap();
+            assert!(file.set_fill_mode(false).unwrap());
+            assert!(!file.set_fill_mode(false).unwrap());
+            file.add_dimension("x", values.len()).unwrap();
+            file.add_variable::<i32>("v", &["x"]).unwrap();
+            file.enddef().unwrap();
+            let mut var = file.variable_mut("v").unwrap();
+            var.put_values(&values, ..).unwrap();
+        }
+        let file = netcdf::open(&path).unwrap();
+        let var = file.variable("v").unwrap();
+        assert_eq!(var.values::<i32, _>(..).unwrap(), values);
+    }
+}
+
+#[test]
+fn open_options() {
+    use netcdf::{Format, OpenOptions};
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("open_options.nc");
+
+    {
+        let mut file = OpenOptions::new()
+            .share(true)
+            .format(Format::Classic)
+            .create(&path)
+            .unwrap();
+        assert_eq!(file.format().unwrap(), Format::Classic);
+        file.add_dimension("x", 2).unwrap();
+        file.add_variable::<i32>("v", &["x"]).unwrap();
+        file.enddef().unwrap();
+        file.variable_mut("v")
+            .unwrap()
+            .put_values(&[1, 2], ..)
+            .unwrap();
+        file.sync().unwrap();
+
+        let reader = OpenOptions::new().share(true).open(&path).unwrap();
+        let var = reader.variable("v").unwrap();
+        assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2]);
+    }
+
+    // Changes to a diskless file are not written back
+    let mut file = OpenOptions::new().diskless(true).append(&path).unwrap();
+    file.redef().unwrap();
+    file.add_dimension("y", 3).unwrap();
+    drop(file);
+    let file = OpenOptions::new().diskless(true).open(&path).unwrap();
+    assert!(file.dimension("x").is_some());
+    assert!(file.dimension("y").is_none());
+    let file = OpenOptions::new()
+        .diskless(true)
+        .write(true)
+        .open(&path)
+        .unwrap();
+    let var = file.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2]);
+
+    let scratch = d.path().join("scratch.nc");
+    let mut file = OpenOptions::new().diskless(true).create(&scratch).unwrap();
+    assert_eq!(file.format().unwrap(), Format::Netcdf4);
+    file.add_group("g").unwrap();
+    drop(file);
+    assert!(!scratch.exists());
+}
+
+#[test]
+fn shared_file() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<netcdf::SharedFile>();
+
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("shared_file.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 100).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+        var.put_values(&(0..100).collect::<Vec<_>>(), ..).unwrap();
+    }
+
+    let file = netcdf::open(&path).unwrap().into_shared();
+    let workers = (0..4)
+        .map(|i| {
+            let file = file.clone();
+            std::thread::spawn(move || {
+                let var = file.variable("v").unwrap();
+                var.values::<i32, _>(i * 25..(i + 1) * 25).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(file);
+    let values = workers
+        .into_iter()
+        .flat_map(|w| w.join().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(values, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+#[cfg(feature = "thread-safe-hdf5")]
+fn concurrent_reads() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let classic = d.path().join("concurrent_reads_classic.nc");
+    let mut file = netcdf::create_with(&classic, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 1).unwrap();
+    drop(file);
+    let file = netcdf::open(&classic).unwrap();
+    let err = unsafe { file.enable_concurrent_reads() }.unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+
+    let paths = ["first", "second"].map(|name| d.path().join(format!("{name}.nc")));
+    for path in &paths {
+        let mut file = netcdf::create(path).unwrap();
+        file.add_dimension("x", 100).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+        var.put_values(&(0..100).collect::<Vec<_>>(), ..).unwrap();
+    }
+    let workers = paths
+        .map(|path| {
+            std::thread::spawn(move || {
+                let file = netcdf::open(path).unwrap();
+                match unsafe { file.enable_concurrent_reads() } {
+                    // hdf5 is not threadsafe, reads take the global lock
+                    Ok(()) | Err(Error::Unsupported(_)) => (),
+                    Err(e) => panic!("{e}"),
+                }
+                let var = file.variable("v").unwrap();
+                (0..10)
+                    .map(|_| var.values::<i32, _>(..).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    for worker in workers {
+        for values in worker.join().unwrap() {
+            assert_eq!(values, (0..100).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn remove_variable_unsupported() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("remove_variable.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 1).unwrap();
+    file.add_variable::<i32>("v", &["x"]).unwrap();
+    let err = file.remove_variable("v").unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+    assert!(err.to_string().contains("copy"));
+    let err = file.remove_variable("w").unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)));
+    assert!(file.variable("v").is_some());
+
+    let mut group = file.add_group("g").unwrap();
+    group.add_variable::<i32>("v", &["x"]).unwrap();
+    let err = group.remove_variable("v").unwrap_err();
+    assert!(matches!(err, Error::Unsupported(_)));
+}
+
+#[test]
+#[cfg(feature = "tokio")]
+fn values_async() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("values_async.nc");
+    let other = d.path().join("values_async_other.nc");
+    for path in [&path, &other] {
+        let mut file = netcdf::create(path).unwrap();
+        file.add_dimension("x", 10).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+        var.put_values(&(0..10).collect::<Vec<_>>(), ..).unwrap();
+    }
+
+    let file = netcdf::open(&path).unwrap().into_shared();
+    let other = netcdf::open(&other).unwrap().into_shared();
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let var = file.variable("v").unwrap();
+        let read = var.values_async::<i32, _>(&file, 2..5);
+        drop(var);
+        assert_eq!(read.await.unwrap(), [2, 3, 4]);
+
+        let var = other.variable("v").unwrap();
+        let err = var.values_async::<i32, _>(&file, ..).await.unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::WrongDataset));
+    });
+}
+
+#[test]
+fn get_or_add() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("get_or_add.nc");
+
+    let init = |file: &mut netcdf::MutableFile| -> netcdf::error::Result<()> {
+        file.get_or_add_dimension("time", 0)?;
+        file.get_or_add_dimension("x", 3)?;
+        file.get_or_add_variable::<f32>("v", &["time", "x"])?;
+        Ok(())
+    };
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        init(&mut file).unwrap();
+        init(&mut file).unwrap();
+        let mut var = file.variable_mut("v").unwrap();
+        var.put_values(&[1.0, 2.0, 3.0], (0, ..)).unwrap();
+    }
+    let mut file = netcdf::append(&path).unwrap();
+    init(&mut file).unwrap();
+    assert_eq!(file.variables().count(), 1);
+    let mut var = file
+        .get_or_add_variable::<f32>("v", &["time", "x"])
+        .unwrap();
+    assert_eq!(var.record_count().unwrap(), 1);
+    var.put_values(&[4.0, 5.0, 6.0], (1, ..)).unwrap();
+
+    // Conflicting definitions
+    let err = file.get_or_add_dimension("x", 4).unwrap_err();
+    assert!(err.to_string().contains("length 3"), "{err}");
+    file.get_or_add_dimension("x", 0).unwrap_err();
+    file.get_or_add_dimension("time", 2).unwrap_err();
+    let err = file
+        .get_or_add_variable::<f64>("v", &["time", "x"])
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+    file.get_or_add_variable::<f32>("v", &["x"]).unwrap_err();
+    file.get_or_add_variable::<f32>("v", &["x", "time"])
+        .unwrap_err();
+    let err = file
+        .get_or_add_variable::<f32>("v", &["time", "y"])
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::NotFound(_)));
+
+    assert_eq!(file.get_or_add_dimension("y", 2).unwrap().len(), 2);
+    file.get_or_add_variable::<i32>("w", &["y"]).unwrap();
+    drop(file);
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("v").unwrap();
+    assert_eq!(
+        var.values::<f32, _>(..).unwrap(),
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+    );
+    assert!(file.variable("w").is_some());
+}
+
+#[test]
+fn file_into_mut() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("into_mut.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 2).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert!(file.attribute("history").is_none());
+    let mut file = file.into_mut().unwrap();
+    file.add_attribute("history", "checked").unwrap();
+    let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+    var.put_values(&[1, 2], ..).unwrap();
+    drop(file);
+
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(
+        file.attribute("history").unwrap().text().unwrap(),
+        "checked"
+    );
+    assert_eq!(
+        file.variable("v").unwrap().values::<i32, _>(..).unwrap(),
+        [1, 2]
+    );
+    drop(file);
+
+    // Changes to diskless files stay in memory
+    let file = netcdf::OpenOptions::new()
+        .diskless(true)
+        .open(&path)
+        .unwrap();
+    let mut file = file.into_mut().unwrap();
+    file.add_dimension("y", 3).unwrap();
+    drop(file);
+    assert!(netcdf::open(&path).unwrap().dimension("y").is_none());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn open_fd() {
+    use std::os::unix::io::AsRawFd;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("open_fd.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 3).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+        var.put_values(&[1, 2, 3], ..).unwrap();
+    }
+    let handle = std::fs::File::open(&path).unwrap();
+    let file = netcdf::open_fd(handle.as_raw_fd()).unwrap();
+    // The library has its own descriptor
+    drop(handle);
+    let var = file.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2, 3]);
+
+    netcdf::open_fd(-1).unwrap_err();
+}
+
+#[test]
+fn verify() {
+    use netcdf::types::VariableType;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("verify.nc");
+    const PATTERN: i32 = 0x1357_9BDF;
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 1024).unwrap();
+        file.add_dimension("y", 7).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        let mut var = file.add_variable::<i32>("protected", &["x"]).unwrap();
+        var.set_chunking(&[1024]).unwrap();
+        var.set_checksum(true).unwrap();
+        var.put_values(&[PATTERN; 1024], ..).unwrap();
+
+        let mut var = file.add_variable::<f64>("grid", &["y", "x"]).unwrap();
+        var.put_values(&vec![1.5; 7 * 1024], ..).unwrap();
+        file.add_variable::<u8>("empty", &["time", "y"]).unwrap();
+        let mut var = file.add_variable::<i16>("scalar", &[]).unwrap();
+        var.put_value(3_i16, ()).unwrap();
+        let mut var = file.add_string_variable("names", &["y"]).unwrap();
+        var.put_string("first", [0]).unwrap();
+        let typ = file.add_vlen_type::<i32>("ragged").unwrap();
+        let mut var = file
+            .add_variable_with_type("rows", &["y"], &VariableType::Vlen(typ))
+            .unwrap();
+        var.put_vlen(&[1, 2, 3], [1]).unwrap();
+
+        let mut group = file.add_group("nested").unwrap();
+        let mut var = group.add_variable::<f32>("g", &["x"]).unwrap();
+        var.put_values(&[0.5; 1024], ..).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    file.verify().unwrap();
+    file.verify_with(100).unwrap();
+    file.verify_with(0).unwrap();
+    drop(file);
+
+    // Corrupt the checksummed chunk
+    let mut bytes = std::fs::read(&path).unwrap();
+    let pattern = [PATTERN; 1024]
+        .iter()
+        .flat_map(|x| x.to_ne_bytes())
+        .collect::<Vec<u8>>();
+    let offset = bytes
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+        .unwrap();
+    bytes[offset + 2000] ^= 0xFF;
+    std::fs::write(&path, bytes).unwrap();
+
+    let file = netcdf::open(&path).unwrap();
+    file.verify().unwrap_err();
+    file.verify_with(100).unwrap_err();
+}
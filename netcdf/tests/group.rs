@@ -64,12 +64,365 @@ fn find_variable() {
 
     for mut var in group.variables_mut() {
         if var.dimensions().len() > 0 {
-            var.compression(3, false).unwrap();
+            var.set_compression(3, false).unwrap();
         }
         if var.name() == "z" {
-            var.chunking(&[1]).unwrap();
+            var.set_chunking(&[1]).unwrap();
         } else {
-            var.chunking(&[]).unwrap();
+            var.set_chunking(&[]).unwrap();
         }
     }
 }
+
+#[test]
+fn group_paths() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("group_paths.nc");
+
+    let mut file = netcdf::create(path).unwrap();
+    let mut grid = file.add_group_path("/model/ocean/grid").unwrap();
+    assert_eq!(grid.name(), "grid");
+    grid.add_dimension("x", 4).unwrap();
+    // Reuses existing groups along the path
+    let atmos = file.add_group_path("model/atmosphere").unwrap();
+    assert_eq!(atmos.name(), "atmosphere");
+    let grid = file.add_group_path("model/ocean/grid").unwrap();
+    assert_eq!(grid.dimension("x").unwrap().len(), 4);
+    file.add_group_path("/").unwrap_err();
+
+    let model = file.group("model").unwrap().unwrap();
+    assert_eq!(model.groups().count(), 2);
+
+    let grid = file.group_path("/model/ocean/grid").unwrap().unwrap();
+    assert_eq!(grid.name(), "grid");
+    assert!(grid.dimension("x").is_some());
+    let ocean = file.group_path("model/ocean").unwrap().unwrap();
+    assert_eq!(ocean.name(), "ocean");
+    assert!(file.group_path("/model/land/grid").unwrap().is_none());
+    assert!(file.group_path("/model/ocean/mesh").unwrap().is_none());
+}
+
+#[test]
+fn subgroup_scoped_items() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("subgroup_scoped_items.nc");
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("time", 2).unwrap();
+
+        let mut group = file.add_group("subgroup").unwrap();
+        group.add_unlimited_dimension("time").unwrap();
+        group.add_dimension("x", 3).unwrap();
+        group.add_attribute("title", "subgroup data").unwrap();
+        let mut var = group.add_variable::<f32>("v", &["time", "x"]).unwrap();
+        var.add_attribute("units", "m").unwrap();
+        var.put_values(&[1.0; 12], [..4, ..3]).unwrap();
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(file.dimension("time").unwrap().len(), 2);
+    assert!(file.dimension("x").is_none());
+    assert!(file.variable("v").is_none());
+
+    let group = file.group("subgroup").unwrap().unwrap();
+    let time = group.dimension("time").unwrap();
+    assert!(time.is_unlimited());
+    assert_eq!(time.len(), 4);
+    assert_eq!(
+        group.attribute("title").unwrap().value().unwrap(),
+        "subgroup data".into()
+    );
+
+    let var = group.variable("v").unwrap();
+    let dims = var.dimensions();
+    assert_eq!(dims[0].name(), "time");
+    assert_eq!(dims[0].len(), 4);
+    assert_eq!(dims[1].len(), 3);
+    assert_eq!(var.values::<f32, _>(..).unwrap(), [1.0; 12]);
+}
+
+#[test]
+fn variables_recursive() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("variables_recursive.nc");
+
+    let mut file = netcdf::create(path).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.add_variable::<i32>("root_var", &["x"]).unwrap();
+    file.add_group_path("/model/ocean")
+        .unwrap()
+        .add_variable::<f32>("temp", &["x"])
+        .unwrap();
+    file.add_group_path("/model/atmosphere")
+        .unwrap()
+        .add_variable::<f32>("wind", &["x"])
+        .unwrap();
+    file.group_mut("model")
+        .unwrap()
+        .unwrap()
+        .add_variable::<u8>("flag", &[])
+        .unwrap();
+    file.add_group("empty").unwrap();
+
+    let mut vars = file
+        .variables_recursive()
+        .map(|item| {
+            let (path, var) = item.unwrap();
+            (path, var.name())
+        })
+        .collect::<Vec<_>>();
+    vars.sort();
+    assert_eq!(
+        vars,
+        [
+            ("/".to_owned(), "root_var".to_owned()),
+            ("/model".to_owned(), "flag".to_owned()),
+            ("/model/atmosphere".to_owned(), "wind".to_owned()),
+            ("/model/ocean".to_owned(), "temp".to_owned()),
+        ]
+    );
+
+    let path = d.path().join("variables_recursive_classic.nc");
+    let mut file = netcdf::create_with(path, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.add_variable::<i32>("v", &["x"]).unwrap();
+    let vars = file
+        .variables_recursive()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(vars.len(), 1);
+    assert_eq!(vars[0].0, "/");
+}
+
+#[test]
+fn dimension_by_identifier() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("dimension_by_identifier.nc");
+    let other = d.path().join("dimension_by_identifier_other.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    let root_x = file.add_dimension("x", 2).unwrap().identifier();
+    let time = file.add_unlimited_dimension("time").unwrap().identifier();
+    let mut g = file.add_group("g").unwrap();
+    let g_x = g.add_dimension("x", 5).unwrap().identifier();
+    let var = g.add_variable::<i32>("v", &["time", "x"]).unwrap();
+    let ids = var
+        .dimensions()
+        .iter()
+        .map(netcdf::Dimension::identifier)
+        .collect::<Vec<_>>();
+
+    let dim = file.dimension_by_id(root_x).unwrap();
+    assert_eq!((dim.name().as_str(), dim.len()), ("x", 2));
+    let dim = file.dimension_by_id(g_x).unwrap();
+    assert_eq!((dim.name().as_str(), dim.len()), ("x", 5));
+    // Dimension of the parent group, seen from the variable
+    let dim = file.dimension_by_id(ids[0]).unwrap();
+    assert_eq!(dim.name(), "time");
+    assert!(dim.is_unlimited());
+    assert!(file.dimension_by_id(time).unwrap().is_unlimited());
+    assert_eq!(file.dimension_by_id(ids[1]).unwrap().len(), 5);
+
+    let mut other = netcdf::create(other).unwrap();
+    let other_x = other.add_dimension("x", 3).unwrap().identifier();
+    assert!(file.dimension_by_id(other_x).is_none());
+}
+
+#[test]
+fn variable_with_parent_dimensions() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("variable_with_parent_dimensions.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        file.add_dimension("x", 2).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["time", "x"]).unwrap();
+        var.put_values(&[1, 2, 3, 4], (..2, ..)).unwrap();
+
+        let time = file.dimension("time").unwrap();
+        let x = file.dimension("x").unwrap();
+        let mut g = file.add_group("g").unwrap();
+        // Shadows the dimension `x` of the root group
+        g.add_dimension("x", 3).unwrap();
+        let mut var = g
+            .add_variable_dims::<i32>("root_x", &[time.clone(), x])
+            .unwrap();
+        assert!(var.dimensions()[0].is_unlimited());
+        assert_eq!(var.dimensions()[0].len(), 2);
+        var.put_values(&[5, 6, 7, 8], ..).unwrap();
+        // Resolved by name to the nearest dimension
+        let var = g.add_variable::<i32>("g_x", &["time", "x"]).unwrap();
+        assert_eq!(var.dimensions()[1].len(), 3);
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let root_x = file.dimension("x").unwrap().identifier();
+    let g = file.group("g").unwrap().unwrap();
+    let var = g.variable("root_x").unwrap();
+    let dims = var.dimensions();
+    assert_eq!(dims[0].name(), "time");
+    assert!(dims[0].is_unlimited());
+    assert_eq!(dims[0].len(), 2);
+    assert_eq!((dims[1].name().as_str(), dims[1].len()), ("x", 2));
+    let dim = file.dimension_by_id(dims[1].identifier()).unwrap();
+    assert_eq!(dim.len(), 2);
+    assert_eq!(file.dimension_by_id(root_x).unwrap().name(), dims[1].name());
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [5, 6, 7, 8]);
+
+    let var = g.variable("g_x").unwrap();
+    assert!(var.dimensions()[0].is_unlimited());
+    assert_eq!(var.dimensions()[1].len(), 3);
+}
+
+#[test]
+fn group_path() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("group_path.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_group_path("/model/ocean").unwrap();
+        file.add_group("æøå").unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let root = file.root().unwrap();
+    assert_eq!(root.path().unwrap(), "/");
+    assert_eq!(root.name(), "/");
+    let ocean = file.group_path("model/ocean").unwrap().unwrap();
+    assert_eq!(ocean.path().unwrap(), "/model/ocean");
+    assert_eq!(ocean.name(), "ocean");
+    let g = file.group("æøå").unwrap().unwrap();
+    assert_eq!(g.path().unwrap(), "/æøå");
+}
+
+#[test]
+fn group_parent() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("group_parent.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("lat", 2).unwrap();
+        file.add_variable::<f32>("lat", &["lat"]).unwrap();
+        file.add_group_path("/model/ocean").unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let ocean = file.group_path("/model/ocean").unwrap().unwrap();
+    let model = ocean.parent().unwrap().unwrap();
+    assert_eq!(model.path().unwrap(), "/model");
+    let root = model.parent().unwrap().unwrap();
+    assert_eq!(root.path().unwrap(), "/");
+    assert!(root.parent().unwrap().is_none());
+
+    // Search upwards for a coordinate variable
+    let mut group = Some(ocean);
+    let mut found = None;
+    while let Some(g) = group {
+        if g.variable("lat").is_some() {
+            found = Some(g.path().unwrap());
+            break;
+        }
+        group = g.parent().unwrap();
+    }
+    assert_eq!(found.as_deref(), Some("/"));
+}
+
+#[test]
+fn counts() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("counts.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        file.add_variable::<i32>("a", &["x"]).unwrap();
+        file.add_variable::<i32>("b", &["time"]).unwrap();
+        file.add_variable::<i32>("c", &[]).unwrap();
+        file.add_attribute("title", "counts").unwrap();
+        let mut group = file.add_group("g").unwrap();
+        group.add_dimension("y", 1).unwrap();
+        group.add_variable::<f32>("v", &["x", "y"]).unwrap();
+        file.add_group("empty").unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert_eq!(file.num_variables().unwrap(), 3);
+    assert_eq!(file.num_dimensions().unwrap(), 2);
+    assert_eq!(file.num_attributes().unwrap(), 1);
+    assert_eq!(file.num_variables().unwrap(), file.variables().count());
+
+    let group = file.group("g").unwrap().unwrap();
+    assert_eq!(group.num_variables().unwrap(), 1);
+    // Dimensions of the parent are not included
+    assert_eq!(group.num_dimensions().unwrap(), 1);
+    assert_eq!(group.num_attributes().unwrap(), 0);
+
+    let group = file.group("empty").unwrap().unwrap();
+    assert_eq!(group.num_variables().unwrap(), 0);
+    assert_eq!(group.num_dimensions().unwrap(), 0);
+}
+
+#[test]
+fn collect_variables_and_groups() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("collect.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        file.add_variable::<i32>("b", &["x"]).unwrap();
+        file.add_variable::<f64>("a", &[]).unwrap();
+        let mut group = file.add_group("g").unwrap();
+        group.add_variable::<u8>("nested", &["x"]).unwrap();
+        file.add_group("h").unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let mut variables = file.collect_variables().unwrap();
+    variables.sort_by_key(netcdf::Variable::name);
+    let names = variables.iter().map(|v| v.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b"]);
+
+    let groups = file.collect_groups().unwrap();
+    let names = groups.iter().map(netcdf::Group::name).collect::<Vec<_>>();
+    assert_eq!(names, ["g", "h"]);
+    assert_eq!(groups[0].variables().count(), 1);
+}
+
+#[test]
+fn variables_in_creation_order() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("creation_order.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        for name in ["zeta", "alpha", "mid"] {
+            file.add_variable::<i32>(name, &["x"]).unwrap();
+        }
+        let mut var = file.variable_mut("alpha").unwrap();
+        var.add_attribute("units", "m").unwrap();
+        var.add_attribute("axis", "X").unwrap();
+        let mut group = file.add_group("g").unwrap();
+        group.add_variable::<u8>("b", &[]).unwrap();
+        group.add_variable::<u8>("a", &[]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let names = file
+        .variables_in_creation_order()
+        .unwrap()
+        .iter()
+        .map(netcdf::Variable::name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["zeta", "alpha", "mid"]);
+    let var = file.variable("alpha").unwrap();
+    let names = var
+        .attributes()
+        .map(|a| a.name().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["units", "axis"]);
+
+    let group = file.group("g").unwrap().unwrap();
+    let names = group
+        .variables_in_creation_order()
+        .unwrap()
+        .iter()
+        .map(netcdf::Variable::name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["b", "a"]);
+}
@@ -168,6 +168,47 @@ fn dimension_lengths() {
     }
 }
 
+#[test]
+fn unlimited_dimensions() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("unlimited_dimensions.nc");
+    let mut file = netcdf::create(path).unwrap();
+
+    let dim = file.add_dimension("x", 3).unwrap();
+    assert!(!dim.is_unlimited());
+    assert_eq!(dim.len(), 3);
+    let dim = file.add_dimension("y", 7).unwrap();
+    assert!(!dim.is_unlimited());
+    assert_eq!(dim.len(), 7);
+    let dim = file.add_unlimited_dimension("time").unwrap();
+    assert!(dim.is_unlimited());
+    assert_eq!(dim.len(), 0);
+
+    let names = file
+        .unlimited_dimensions()
+        .map(|d| d.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["time"]);
+
+    let mut var = file.add_variable::<i32>("v", &["time", "x"]).unwrap();
+    var.put_values(&[1, 2, 3, 4, 5, 6], [..2, ..3]).unwrap();
+
+    let time = file.dimension("time").unwrap();
+    assert!(time.is_unlimited());
+    assert_eq!(time.len(), 2);
+    assert!(!file.dimension("x").unwrap().is_unlimited());
+
+    let mut group = file.add_group("grp").unwrap();
+    group.add_dimension("z", 2).unwrap();
+    group.add_unlimited_dimension("record").unwrap();
+    let group = file.group("grp").unwrap().unwrap();
+    let names = group
+        .unlimited_dimensions()
+        .map(|d| d.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["record"]);
+}
+
 #[test]
 fn netcdf_error() {
     let path = ".";
@@ -194,6 +235,36 @@ fn netcdf_error() {
     file.add_unlimited_dimension("v").unwrap_err();
 }
 
+#[test]
+#[cfg(feature = "ndarray")]
+fn ndarray_put_arr() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("ndarray_put_arr.nc");
+    let mut file = netcdf::create(path).unwrap();
+    file.add_dimension("y", 3).unwrap();
+    file.add_dimension("x", 4).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["y", "x"]).unwrap();
+
+    let arr = ndarray::Array::from_shape_vec((3, 4), (0..12).collect()).unwrap();
+    var.put_arr(arr.view(), ..).unwrap();
+    let values = var.values_arr::<i32, _>(..).unwrap();
+    assert_eq!(values, arr.clone().into_dyn());
+
+    // Non-contiguous view
+    var.put_arr(arr.slice(ndarray::s![.., ..;2]), (.., ..2))
+        .unwrap();
+    let values = var.values_arr::<i32, _>((.., ..2)).unwrap();
+    assert_eq!(values, arr.slice(ndarray::s![.., ..;2]).into_dyn());
+    var.put_arr(arr.slice(ndarray::s![..;-1, ..3]).t(), (..3, ..3))
+        .unwrap();
+    let values = var.values_arr::<i32, _>((..3, ..3)).unwrap();
+    assert_eq!(values, arr.slice(ndarray::s![..;-1, ..3]).t().into_dyn());
+
+    // Shape must match the extents
+    var.put_arr(arr.view(), (..2, ..)).unwrap_err();
+    var.put_arr(arr.slice(ndarray::s![0, ..]), ..).unwrap_err();
+}
+
 #[test]
 #[cfg(feature = "ndarray")]
 fn ndarray_read_with_indices() {
@@ -814,6 +885,22 @@ fn more_fill_values() {
     // assert!(var.attribute("_FillValue").is_none());
 }
 
+#[test]
+fn fill_value_type_mismatch() {
+    let d = tempfile::tempdir().expect("Could not create tempdir");
+    let path = d.path().join("fill_value_type_mismatch.nc");
+    let mut file = netcdf::create(path).expect("Could not open file");
+
+    file.add_dimension("x", 2).unwrap();
+    let var = &mut file.add_variable::<i16>("v", &["x"]).unwrap();
+    // Fill value must have the same type as the variable
+    assert!(matches!(
+        var.set_fill_value(2.0_f64).unwrap_err(),
+        netcdf::error::Error::TypeMismatch
+    ));
+    assert!(var.attribute("_FillValue").is_none());
+}
+
 #[test]
 /// Test reading a slice of a variable into a buffer
 fn read_slice_into_buffer() {
@@ -833,6 +920,30 @@ fn read_slice_into_buffer() {
     }
 }
 
+#[test]
+fn read_into_reused_buffer() {
+    let f = test_location().join("simple_xy.nc");
+    let file = netcdf::open(f).unwrap();
+    let var = &file.variable("data").expect("Could not find variable");
+
+    let mut buffer = [0i32; 12];
+    for row in 0..6 {
+        var.values_to(&mut buffer, (&[row, 0], &[1, 12])).unwrap();
+        for (i, v) in buffer.iter().enumerate() {
+            assert_eq!(*v, (row * 12 + i) as i32);
+        }
+    }
+
+    let mut small = [0i32; 11];
+    match var.values_to(&mut small, (&[0, 0], &[1, 12])).unwrap_err() {
+        netcdf::error::Error::BufferLen { wanted, actual } => {
+            assert_eq!(wanted, 12);
+            assert_eq!(actual, 11);
+        }
+        e => panic!("Unexpected error {:?}", e),
+    }
+}
+
 #[test]
 #[should_panic]
 fn read_mismatched() {
@@ -854,8 +965,8 @@ fn use_compression_chunking() {
     file.add_dimension("x", 10).unwrap();
 
     let var = &mut file.add_variable::<i32>("compressed", &["x"]).unwrap();
-    var.compression(5, false).unwrap();
-    var.chunking(&[5]).unwrap();
+    var.set_compression(5, false).unwrap();
+    var.set_chunking(&[5]).unwrap();
 
     let v = vec![0i32; 10];
     var.put_values(&v, ..).unwrap();
@@ -863,14 +974,14 @@ fn use_compression_chunking() {
     let var = &mut file
         .add_variable::<i32>("compressed2", &["x", "x"])
         .unwrap();
-    var.compression(9, true).unwrap();
-    var.chunking(&[5, 5]).unwrap();
+    var.set_compression(9, true).unwrap();
+    var.set_chunking(&[5, 5]).unwrap();
     var.put_values(&[1i32, 2, 3, 4, 5, 6, 7, 8, 9, 10], (..10, ..1))
         .unwrap();
 
     let var = &mut file.add_variable::<i32>("chunked3", &["x"]).unwrap();
     assert!(
-        if let netcdf::error::Error::SliceLen = var.chunking(&[2, 2]).unwrap_err() {
+        if let netcdf::error::Error::SliceLen = var.set_chunking(&[2, 2]).unwrap_err() {
             true
         } else {
             false
@@ -880,7 +991,174 @@ fn use_compression_chunking() {
     file.add_dimension("y", 0).unwrap();
     let var = &mut file.add_variable::<u8>("chunked4", &["y", "x"]).unwrap();
 
-    var.chunking(&[100, 2]).unwrap();
+    var.set_chunking(&[100, 2]).unwrap();
+}
+
+#[test]
+fn compression_level() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("compression_level.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 10).unwrap();
+
+        let mut var = file.add_variable::<i32>("plain", &["x"]).unwrap();
+        assert_eq!(var.compression().unwrap(), None);
+        var.set_compression(10, false).unwrap_err();
+        var.put_values(&[1; 10], ..).unwrap();
+        // Data has been written
+        var.set_compression(2, false).unwrap_err();
+
+        let mut var = file.add_variable::<i32>("compressed", &["x"]).unwrap();
+        var.set_compression(4, true).unwrap();
+        assert_eq!(var.compression().unwrap(), Some((4, true)));
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("compressed").unwrap();
+    assert_eq!(var.compression().unwrap(), Some((4, true)));
+    let var = file.variable("plain").unwrap();
+    assert_eq!(var.compression().unwrap(), None);
+}
+
+#[test]
+fn chunking_layout() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("chunking_layout.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 10).unwrap();
+        file.add_dimension("y", 4).unwrap();
+        file.add_unlimited_dimension("t").unwrap();
+
+        let mut var = file.add_variable::<i32>("contiguous", &["x", "y"]).unwrap();
+        assert_eq!(var.chunking().unwrap(), None);
+        var.set_chunking(&[5]).unwrap_err();
+        var.set_chunking(&[5, 5]).unwrap_err();
+
+        let mut var = file
+            .add_variable::<i32>("chunked", &["t", "x", "y"])
+            .unwrap();
+        var.set_chunking(&[100, 5, 4]).unwrap();
+        assert_eq!(var.chunking().unwrap(), Some(vec![100, 5, 4]));
+
+        let mut var = file.add_variable::<i32>("scalar", &[]).unwrap();
+        var.set_chunking(&[]).unwrap();
+        assert_eq!(var.chunking().unwrap(), None);
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("chunked").unwrap();
+    assert_eq!(var.chunking().unwrap(), Some(vec![100, 5, 4]));
+    let var = file.variable("contiguous").unwrap();
+    assert_eq!(var.chunking().unwrap(), None);
+}
+
+#[test]
+fn checksum() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("checksum.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 10).unwrap();
+
+        let mut var = file.add_variable::<i32>("checked", &["x"]).unwrap();
+        assert!(!var.checksum().unwrap());
+        var.set_checksum(true).unwrap();
+        assert!(var.checksum().unwrap());
+        var.put_values(&[4; 10], ..).unwrap();
+
+        let mut var = file.add_variable::<i32>("unchecked", &["x"]).unwrap();
+        var.put_values(&[4; 10], ..).unwrap();
+        // Data has been written
+        var.set_checksum(true).unwrap_err();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("checked").unwrap();
+    assert!(var.checksum().unwrap());
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [4; 10]);
+    assert!(!file.variable("unchecked").unwrap().checksum().unwrap());
+}
+
+#[test]
+fn rename_variable() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("rename_variable.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 3).unwrap();
+        file.add_variable::<i32>("other", &["x"]).unwrap();
+        let mut var = file.add_variable::<i32>("misnamed", &["x"]).unwrap();
+        var.put_values(&[1, 2, 3], ..).unwrap();
+        var.rename("renamed").unwrap();
+        assert_eq!(var.name(), "renamed");
+        let err = var.rename("other").unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::NameInUse { .. }));
+        assert_eq!(err.code(), Some(netcdf_sys::NC_ENAMEINUSE));
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert!(file.variable("misnamed").is_none());
+    let var = file.variable("renamed").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn rename_dimension() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("rename_dimension.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 3).unwrap();
+        file.add_dimension("y", 2).unwrap();
+        file.add_variable::<f32>("x", &["x"]).unwrap();
+
+        file.rename_dimension("x", "lon").unwrap();
+        assert!(file.dimension("x").is_none());
+        assert_eq!(file.dimension("lon").unwrap().len(), 3);
+        assert!(matches!(
+            file.rename_dimension("x", "lat").unwrap_err(),
+            netcdf::error::Error::NotFound(_)
+        ));
+        let err = file.rename_dimension("y", "lon").unwrap_err();
+        assert!(matches!(
+            err,
+            netcdf::error::Error::NameInUse {
+                kind: "dimension",
+                ..
+            }
+        ));
+        // Keep the coordinate variable in sync with the dimension
+        let mut var = file.variable_mut("x").unwrap();
+        var.rename("lon").unwrap();
+        var.put_values(&[0.0, 0.5, 1.0], ..).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("lon").unwrap();
+    assert_eq!(var.dimensions()[0].name(), "lon");
+    assert_eq!(var.values::<f32, _>(..).unwrap(), [0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn rename_variable_classic() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("rename_variable_classic.nc");
+    {
+        let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+        file.add_dimension("x", 3).unwrap();
+        file.add_variable::<i32>("v", &["x"]).unwrap();
+    }
+    {
+        // Opened in data mode, renaming to a longer name requires define mode
+        let mut file = netcdf::append(&path).unwrap();
+        let mut var = file.variable_mut("v").unwrap();
+        var.rename("a_much_longer_name").unwrap();
+        assert_eq!(var.name(), "a_much_longer_name");
+        var.rename("w").unwrap();
+        assert_eq!(var.name(), "w");
+        file.rename_dimension("x", "a_much_longer_dimension")
+            .unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    assert!(file.variable("w").is_some());
+    assert!(file.dimension("a_much_longer_dimension").is_some());
 }
 
 #[test]
@@ -903,7 +1181,7 @@ fn set_compression_all_variables_in_a_group() {
         .expect("Could not create variable");
 
     for mut var in file.variables_mut() {
-        var.compression(9, false)
+        var.set_compression(9, false)
             .expect("Could not set compression level");
     }
 }
@@ -933,6 +1211,63 @@ fn read_from_memory() {
     }
 }
 
+#[test]
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+fn create_in_memory() {
+    for (options, magic) in [
+        (netcdf::Options::CLASSIC, &b"CDF"[..]),
+        (netcdf::Options::NETCDF4, &b"\x89HDF"[..]),
+    ] {
+        let mut file = netcdf::create_mem_with(None, 1024, options).unwrap();
+        file.add_dimension("x", 5).unwrap();
+        file.add_variable::<i32>("v", &["x"]).unwrap();
+        let bytes = file.into_bytes().unwrap();
+        assert!(bytes.starts_with(magic));
+
+        let file = netcdf::open_mem(None, &bytes).unwrap();
+        assert_eq!(file.dimension("x").unwrap().len(), 5);
+        assert!(file.variable("v").is_some());
+    }
+
+    let mut file = netcdf::create_mem(None, 0).unwrap();
+    file.add_dimension("x", 3).unwrap();
+    let mut var = file.add_variable::<f64>("v", &["x"]).unwrap();
+    var.put_values(&[0.5, 1.5, 2.5], ..).unwrap();
+    let bytes = file.into_bytes().unwrap();
+    assert!(bytes.starts_with(b"\x89HDF"));
+
+    let file = netcdf::open_mem(None, &bytes).unwrap();
+    let var = file.variable("v").unwrap();
+    let mut values = [0.0; 3];
+    var.values_to(&mut values, ..).unwrap();
+    assert_eq!(values, [0.5, 1.5, 2.5]);
+}
+
+#[test]
+#[cfg(all(feature = "has-mmap", feature = "4.6.2"))]
+fn finish_in_memory() {
+    for options in [netcdf::Options::CLASSIC, netcdf::Options::NETCDF4] {
+        let mut file = netcdf::create_mem_with(None, 0, options).unwrap();
+        file.add_dimension("x", 3).unwrap();
+        file.add_variable::<i16>("v", &["x"]).unwrap();
+        file.enddef().unwrap();
+        file.variable_mut("v")
+            .unwrap()
+            .put_values(&[1_i16, 2, 3], ..)
+            .unwrap();
+
+        let read = file.as_read();
+        assert_eq!(read.dimension("x").unwrap().len(), 3);
+        let var = read.variable("v").unwrap();
+        assert_eq!(var.values::<i16, _>(..).unwrap(), [1, 2, 3]);
+
+        let bytes = file.finish().unwrap();
+        let file = netcdf::open_mem(None, &bytes).unwrap();
+        let var = file.variable("v").unwrap();
+        assert_eq!(var.values::<i16, _>(..).unwrap(), [1, 2, 3]);
+    }
+}
+
 #[test]
 fn add_conflicting_dimensions() {
     let d = tempfile::tempdir().unwrap();
@@ -942,9 +1277,10 @@ fn add_conflicting_dimensions() {
     file.add_dimension("x", 10).unwrap();
     let e = file.add_dimension("x", 11).unwrap_err();
     assert!(match e {
-        netcdf::error::Error::AlreadyExists => true,
+        netcdf::error::Error::NameInUse { kind, ref name } => kind == "dimension" && name == "x",
         _ => false,
     });
+    assert_eq!(e.code(), Some(netcdf_sys::NC_ENAMEINUSE));
     assert_eq!(file.dimension("x").unwrap().len(), 10);
 }
 
@@ -960,8 +1296,8 @@ fn add_conflicting_variables() {
 
     let e = file.add_variable::<f32>("x", &["y"]).unwrap_err();
     assert!(match e {
-        netcdf::error::Error::AlreadyExists => {
-            true
+        netcdf::error::Error::NameInUse { kind, name } => {
+            kind == "variable" && name == "x"
         }
         e => {
             panic!("{}", e)
@@ -1069,6 +1405,15 @@ fn length_of_variable() {
     let var = &mut file.add_variable::<f64>("z", &["x", "z"]).unwrap();
     var.put_value(1u8, &[2, 8]).unwrap();
     assert_eq!(var.len(), 4 * 9);
+    assert_eq!(var.shape(), [4, 9]);
+
+    let mut buffer = vec![0.0; var.len()];
+    var.values_to(&mut buffer, ..).unwrap();
+    assert_eq!(buffer[2 * 9 + 8], 1.0);
+
+    let var = file.add_variable::<i32>("scalar", &[]).unwrap();
+    assert_eq!(var.len(), 1);
+    assert!(var.shape().is_empty());
 }
 
 #[test]
@@ -1165,6 +1510,36 @@ fn string_variables() {
     var.string_value([1]).unwrap_err();
 }
 
+#[test]
+fn string_variables_bulk() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("string_variables_bulk.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        file.add_dimension("y", 3).unwrap();
+        let var = &mut file.add_string_variable("str", &["x", "y"]).unwrap();
+
+        var.put_strings(&["a", "bb", "ccc", "", "ß", "f"], ..)
+            .unwrap();
+        let strings = vec![String::from("g"), String::from("h")];
+        var.put_strings(&strings, (1, 1..)).unwrap();
+        var.put_strings(&["too", "few"], ..).unwrap_err();
+        var.put_strings(&["nul\0"], (0, 0)).unwrap_err();
+    }
+    let file = netcdf::open(path).unwrap();
+    let var = &file.variable("str").unwrap();
+    assert_eq!(
+        var.string_values(..).unwrap(),
+        ["a", "bb", "ccc", "", "g", "h"]
+    );
+    assert_eq!(var.string_values((.., 1)).unwrap(), ["bb", "g"]);
+    assert_eq!(
+        var.string_values((0, (0..).step_by(2))).unwrap(),
+        ["a", "ccc"]
+    );
+}
+
 #[test]
 fn unlimited_in_parents() {
     let d = tempfile::tempdir().expect("Could not create tempdir");
@@ -1295,22 +1670,38 @@ fn set_get_endian() {
             let var = &mut file_w
                 .add_variable::<i32>("some_variable", &[dim_name])
                 .unwrap();
-            var.endian(*i).unwrap();
-            assert_eq!(var.endian_value().unwrap(), *i);
+            var.set_endianness(*i).unwrap();
+            assert_eq!(var.endianness().unwrap(), *i);
             var.put_values::<i32, _>(&[1, 2, 3], ..).unwrap();
             // close it (done when `file_w` goes out of scope)
         }
         {
             // re-open it
-            // and get "some variable" endian_value
+            // and get "some variable" endianness
             let file_o = netcdf::open(&f).unwrap();
             let var = &file_o.variable("some_variable").unwrap();
-            assert_eq!(var.endian_value().unwrap(), *i);
+            assert_eq!(var.endianness().unwrap(), *i);
             // close it (done when `file_a` goes out of scope)
         }
     }
 }
 
+#[test]
+fn endianness_classic() {
+    use netcdf::variable::Endianness;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("endianness_classic.nc");
+    let mut file = netcdf::create_with(path, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 3).unwrap();
+    let var = &mut file.add_variable::<i32>("v", &["x"]).unwrap();
+    // Only supported for netCDF-4 files
+    let err = var.set_endianness(Endianness::Big).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::Unsupported(_)));
+    assert!(err.to_string().contains("classic"));
+    let err = var.endianness().unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::Unsupported(_)));
+}
+
 mod strided {
     #[test]
     fn get_to_buffer() {
@@ -1335,9 +1726,15 @@ mod strided {
         )
         .unwrap();
         assert_eq!(&buffer, &(0..3 * 5 * 9).collect::<Vec<_>>());
-        // Negative and zero strides seems not to be supported
-        // var.values_strided_to(&mut buffer, None, None, &[0, 0, 0]).unwrap();
-        // var.values_strided_to(&mut buffer, None, None, &[-1, -1, -1]).unwrap();
+        // Negative and zero strides are not supported
+        let err = var
+            .values_to(&mut buffer, (&[0, 0, 0], &[3, 5, 9], &[0, 1, 1]))
+            .unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::Stride));
+        let err = var
+            .values_to(&mut buffer, (&[2, 4, 8], &[3, 5, 9], &[-1, -1, -1]))
+            .unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::Stride));
         let mut buffer = vec![0; 3 * 5 * 2];
         var.values_to(
             &mut buffer,
@@ -1436,6 +1833,24 @@ mod strided {
         assert_eq!(&buffer, &[94, 97, 103, 106]);
     }
     #[test]
+    fn get_every_other() {
+        let d = tempfile::tempdir().unwrap();
+        let name = d.path().join("strided_every_other.nc");
+        let mut file = netcdf::create(name).unwrap();
+        file.add_dimension("x", 10).unwrap();
+        let mut var = file.add_variable::<i32>("data", &["x"]).unwrap();
+        var.put_values(&(0..10).collect::<Vec<_>>(), ..).unwrap();
+
+        let values = var.values::<i32, _>((&[0], &[5], &[2])).unwrap();
+        assert_eq!(values, [0, 2, 4, 6, 8]);
+        let values = var.values::<i32, _>([(0..).step_by(2)]).unwrap();
+        assert_eq!(values, [0, 2, 4, 6, 8]);
+
+        var.values::<i32, _>((&[0], &[5], &[0])).unwrap_err();
+        var.values::<i32, _>((&[0, 0], &[5, 1], &[2, 1]))
+            .unwrap_err();
+    }
+    #[test]
     fn put_buffer() {
         let d = tempfile::tempdir().unwrap();
         let path = d.path().join("put_strided_buffer.nc");
@@ -1603,3 +2018,1511 @@ fn invalid_utf8_as_path() {
     let retrieved_path = file.path().unwrap();
     assert_eq!(fullpath, retrieved_path);
 }
+
+#[test]
+fn read_unpacked_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("unpacked_values.nc");
+    let mut file = netcdf::create(path).unwrap();
+    file.add_dimension("x", 5).unwrap();
+
+    let mut var = file.add_variable::<i16>("packed", &["x"]).unwrap();
+    var.set_fill_value(-1_i16).unwrap();
+    var.add_attribute("missing_value", -2_i16).unwrap();
+    var.add_attribute("scale_factor", 0.5_f64).unwrap();
+    var.add_attribute("add_offset", 10.0_f32).unwrap();
+    var.put_values(&[0_i16, 1, -2], ..3).unwrap();
+
+    let values = var.values_unpacked(..).unwrap();
+    assert_eq!(&values[..2], &[10.0, 10.5]);
+    assert!(values[2..].iter().all(|v| v.is_nan()));
+    assert_eq!(var.values_unpacked(1..2).unwrap(), [10.5]);
+
+    let mut var = file.add_variable::<u8>("plain", &["x"]).unwrap();
+    var.put_values(&[1_u8, 2, 3, 4, 5], ..).unwrap();
+    assert_eq!(var.values_unpacked(..).unwrap(), [1.0, 2.0, 3.0, 4.0, 5.0]);
+    var.add_attribute("scale_factor", "not a number").unwrap();
+    var.values_unpacked(..).unwrap_err();
+}
+
+#[test]
+fn iterate_values() {
+    let f = test_location().join("simple_xy.nc");
+    let file = netcdf::open(f).unwrap();
+    let var = &file.variable("data").expect("Could not find variable");
+
+    let expected = (0..6 * 12).collect::<Vec<i32>>();
+    let values = var
+        .value_iter::<i32>()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, expected);
+    for rows in [0, 1, 4, 6, 100] {
+        let values = var
+            .value_iter::<i32>()
+            .unwrap()
+            .with_chunk_rows(rows)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values, expected);
+    }
+
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("iterate_values.nc");
+    let mut file = netcdf::create(path).unwrap();
+    let mut var = file.add_variable::<f32>("scalar", &[]).unwrap();
+    var.put_value(4.0_f32, ()).unwrap();
+    let values = var
+        .value_iter::<f32>()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [4.0]);
+
+    file.add_dimension("x", 10).unwrap();
+    let mut var = file.add_variable::<u8>("chunked", &["x"]).unwrap();
+    var.set_chunking(&[3]).unwrap();
+    var.put_values(&[1; 10], ..).unwrap();
+    let values = var
+        .value_iter::<u8>()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [1; 10]);
+}
+
+#[test]
+fn scalar_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("scalar_values.nc");
+    let mut file = netcdf::create(path).unwrap();
+    let mut var = file.add_variable::<f64>("scalar", &[]).unwrap();
+    var.put_value(3.5, ()).unwrap();
+    assert_eq!(var.value::<f64, _>(()).unwrap(), 3.5);
+
+    file.add_dimension("x", 2).unwrap();
+    let mut var = file.add_variable::<f64>("vector", &["x"]).unwrap();
+    var.put_value(1.0, ()).unwrap_err();
+    assert!(matches!(
+        var.value::<f64, _>(()).unwrap_err(),
+        netcdf::error::Error::DimensionMismatch {
+            wanted: 1,
+            actual: 0
+        }
+    ));
+}
+
+#[test]
+fn append_records() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("append_records.nc");
+    let mut file = netcdf::create(path).unwrap();
+
+    file.add_unlimited_dimension("time").unwrap();
+    file.add_dimension("lat", 2).unwrap();
+    file.add_dimension("lon", 3).unwrap();
+    let mut var = file
+        .add_variable::<i32>("v", &["time", "lat", "lon"])
+        .unwrap();
+
+    for step in 0..3 {
+        let values = (0..6).map(|i| step * 10 + i).collect::<Vec<i32>>();
+        var.append(&values).unwrap();
+        assert_eq!(var.dimensions()[0].len(), step as usize + 1);
+    }
+    assert_eq!(var.len(), 18);
+    assert_eq!(
+        var.values::<i32, _>([2..3, 1..2, 0..3]).unwrap(),
+        [23, 24, 25]
+    );
+
+    assert!(matches!(
+        var.append(&[1, 2, 3]).unwrap_err(),
+        netcdf::error::Error::BufferLen {
+            wanted: 6,
+            actual: 3
+        }
+    ));
+    assert_eq!(file.dimension("time").unwrap().len(), 3);
+
+    let mut fixed = file.add_variable::<i32>("fixed", &["lat", "lon"]).unwrap();
+    fixed.append(&[0; 3]).unwrap_err();
+}
+
+#[test]
+fn values_as_converted() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("values_as_converted.nc");
+    let mut file = netcdf::create(path).unwrap();
+    file.add_dimension("x", 3).unwrap();
+
+    let mut var = file.add_variable::<i32>("counts", &["x"]).unwrap();
+    var.put_values(&[1, -20, 300], ..).unwrap();
+
+    assert_eq!(
+        var.values_as::<f64, _>(.., false).unwrap(),
+        [1.0, -20.0, 300.0]
+    );
+    assert_eq!(var.values_as::<i32, _>(1.., true).unwrap(), [-20, 300]);
+    assert!(matches!(
+        var.values_as::<f64, _>(.., true).unwrap_err(),
+        netcdf::error::Error::TypeMismatch
+    ));
+    assert!(matches!(
+        var.values_as::<i8, _>(.., false).unwrap_err(),
+        netcdf::error::Error::Netcdf(netcdf_sys::NC_ERANGE)
+    ));
+}
+
+#[test]
+fn read_all_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("read_all_values.nc");
+    let mut file = netcdf::create(path).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.add_dimension("y", 3).unwrap();
+
+    let mut var = file.add_variable::<u16>("grid", &["x", "y"]).unwrap();
+    var.put_values(&[1, 2, 3, 4, 5, 6], ..).unwrap();
+    assert_eq!(var.values_all::<u16>().unwrap(), [1, 2, 3, 4, 5, 6]);
+    assert_eq!(
+        var.values_all::<f32>().unwrap(),
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+    );
+
+    let mut var = file.add_variable::<f64>("scalar", &[]).unwrap();
+    var.put_value(2.5, ()).unwrap();
+    assert_eq!(var.values_all::<f64>().unwrap(), [2.5]);
+}
+
+#[test]
+fn variable_chunk_cache() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("variable_chunk_cache.nc");
+    let mut file = netcdf::create(path).unwrap();
+    file.add_dimension("x", 100).unwrap();
+
+    let mut var = file.add_variable::<f32>("v", &["x"]).unwrap();
+    var.set_chunking(&[10]).unwrap();
+    var.set_compression(4, false).unwrap();
+    var.set_chunk_cache(64 * 1024 * 1024, 1009, 0.5).unwrap();
+    let (size, nelems, preemption) = var.chunk_cache().unwrap();
+    assert_eq!(size, 64 * 1024 * 1024);
+    assert_eq!(nelems, 1009);
+    assert!((preemption - 0.5).abs() < 1e-6);
+
+    var.set_chunk_cache(1024, 7, 1.5).unwrap_err();
+    var.set_chunk_cache(1024, 7, -0.1).unwrap_err();
+    var.set_chunk_cache(1024, 7, f32::NAN).unwrap_err();
+    assert_eq!(var.chunk_cache().unwrap().0, 64 * 1024 * 1024);
+
+    let path = d.path().join("variable_chunk_cache_classic.nc");
+    let mut file = netcdf::create_with(path, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 10).unwrap();
+    let mut var = file.add_variable::<f32>("v", &["x"]).unwrap();
+    assert!(matches!(
+        var.set_chunk_cache(1024, 7, 0.5).unwrap_err(),
+        netcdf::error::Error::Netcdf(netcdf_sys::NC_ENOTNC4)
+    ));
+    var.chunk_cache().unwrap_err();
+}
+
+#[test]
+fn default_chunk_cache() {
+    let original = netcdf::get_chunk_cache().unwrap();
+
+    netcdf::set_chunk_cache(32 * 1024 * 1024, 2003, 0.25).unwrap();
+    let (size, nelems, preemption) = netcdf::get_chunk_cache().unwrap();
+    assert_eq!(size, 32 * 1024 * 1024);
+    assert_eq!(nelems, 2003);
+    assert!((preemption - 0.25).abs() < 1e-6);
+
+    let d = tempfile::tempdir().unwrap();
+    let mut file = netcdf::create(d.path().join("default_chunk_cache.nc")).unwrap();
+    file.add_dimension("x", 10).unwrap();
+    let var = file.add_variable::<f32>("v", &["x"]).unwrap();
+    assert_eq!(var.chunk_cache().unwrap().0, 32 * 1024 * 1024);
+
+    netcdf::set_chunk_cache(1024, 7, 2.0).unwrap_err();
+    netcdf::set_chunk_cache(original.0, original.1, original.2).unwrap();
+}
+
+#[test]
+#[cfg(feature = "4.9.0")]
+fn quantization() {
+    use netcdf::QuantizeMode;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("quantization.nc");
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 4).unwrap();
+
+        let mut var = file.add_variable::<f32>("bitgroom", &["x"]).unwrap();
+        assert_eq!(var.quantization().unwrap(), None);
+        var.set_quantization(QuantizeMode::BitGroom, 3).unwrap();
+        var.put_values(&[1.23456, 2.34567, 3.45678, 4.56789], ..)
+            .unwrap();
+
+        let mut var = file.add_variable::<f64>("bitround", &["x"]).unwrap();
+        var.set_quantization(QuantizeMode::BitRound, 10).unwrap();
+        let mut var = file.add_variable::<f64>("granular", &["x"]).unwrap();
+        var.set_quantization(QuantizeMode::GranularBitRound, 20)
+            .unwrap_err();
+
+        let mut var = file.add_variable::<i32>("int", &["x"]).unwrap();
+        assert!(matches!(
+            var.set_quantization(QuantizeMode::BitGroom, 3).unwrap_err(),
+            netcdf::error::Error::TypeMismatch
+        ));
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("bitgroom").unwrap();
+    assert_eq!(
+        var.quantization().unwrap(),
+        Some((QuantizeMode::BitGroom, 3))
+    );
+    for (value, expected) in var
+        .values::<f32, _>(..)
+        .unwrap()
+        .into_iter()
+        .zip([1.23456, 2.34567, 3.45678, 4.56789])
+    {
+        assert!((value - expected).abs() < 0.01);
+    }
+    let var = file.variable("bitround").unwrap();
+    assert_eq!(
+        var.quantization().unwrap(),
+        Some((QuantizeMode::BitRound, 10))
+    );
+}
+
+#[test]
+#[cfg(feature = "4.7.4")]
+fn szip_compression() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("szip_compression.nc");
+    let values = (0..64).map(|i| i as f32).collect::<Vec<_>>();
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 64).unwrap();
+        let mut var = file.add_variable::<f32>("v", &["x"]).unwrap();
+        var.set_szip(netcdf_sys::NC_SZIP_NN as u32, 7).unwrap_err();
+        var.set_szip(netcdf_sys::NC_SZIP_NN as u32, 64).unwrap_err();
+
+        let result = var.set_szip(netcdf_sys::NC_SZIP_NN as u32, 8);
+        if cfg!(not(feature = "has-szip")) {
+            assert!(result.unwrap_err().to_string().contains("szip"));
+            return;
+        }
+        result.unwrap();
+        var.put_values(&values, ..).unwrap();
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("v").unwrap();
+    assert_eq!(var.values::<f32, _>(..).unwrap(), values);
+}
+
+#[test]
+#[cfg(feature = "4.8.0")]
+fn hdf5_filters() {
+    // Filters built into hdf5
+    const DEFLATE: u32 = 1;
+    const SHUFFLE: u32 = 2;
+    const ZSTD: u32 = 32015;
+
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("hdf5_filters.nc");
+    let values = (0..100).collect::<Vec<i32>>();
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 100).unwrap();
+        let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+        assert!(var.filters().unwrap().is_empty());
+        var.set_filter(SHUFFLE, &[]).unwrap();
+        var.set_filter(DEFLATE, &[5]).unwrap();
+        var.put_values(&values, ..).unwrap();
+
+        let mut var = file.add_variable::<i32>("w", &["x"]).unwrap();
+        if var.set_filter(ZSTD, &[3]).is_ok() {
+            assert_eq!(var.filters().unwrap(), [(ZSTD, vec![3])]);
+        }
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("v").unwrap();
+    let filters = var.filters().unwrap();
+    assert_eq!(filters.len(), 2);
+    assert_eq!(filters[0].0, SHUFFLE);
+    assert_eq!(filters[1], (DEFLATE, vec![5]));
+    assert_eq!(var.compression().unwrap(), Some((5, true)));
+    assert_eq!(var.values::<i32, _>(..).unwrap(), values);
+}
+
+#[test]
+fn put_values_strided() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("put_values_strided.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 5).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+    var.set_fill_value(-1).unwrap();
+    var.put_values_strided(&[10, 20, 30], &[0], &[3], &[2])
+        .unwrap();
+
+    let err = var
+        .put_values_strided(&[10, 20], &[0], &[3], &[2])
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        netcdf::error::Error::BufferLen {
+            wanted: 3,
+            actual: 2
+        }
+    ));
+    let err = var.put_values_strided(&[1], &[0], &[1], &[0]).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::Stride));
+    let err = var
+        .put_values_strided(&[1, 2], &[4], &[2], &[-2])
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::Stride));
+    let err = var
+        .put_values_strided(&[1, 2, 3], &[1], &[3], &[2])
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        netcdf::error::Error::OutOfBounds {
+            dim: 0,
+            start: 1,
+            count: 3,
+            len: 5
+        }
+    ));
+    let err = var
+        .put_values_strided(&[1], &[0, 0], &[1, 1], &[1])
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::SliceLen));
+    drop(file);
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("v").unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [10, -1, 20, -1, 30]);
+}
+
+#[test]
+fn mapped_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("mapped_values.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("y", 2).unwrap();
+    file.add_dimension("x", 3).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["y", "x"]).unwrap();
+    // Column-major buffer
+    let column_major = [0, 3, 1, 4, 2, 5];
+    var.put_values_mapped(&column_major, &[0, 0], &[2, 3], &[1, 1], &[1, 2])
+        .unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [0, 1, 2, 3, 4, 5]);
+
+    let transposed = var
+        .values_mapped::<i32>(&[0, 0], &[2, 3], &[1, 1], &[1, 2])
+        .unwrap();
+    assert_eq!(transposed, column_major);
+    let row_major = var
+        .values_mapped::<i32>(&[0, 0], &[2, 3], &[1, 1], &[3, 1])
+        .unwrap();
+    assert_eq!(row_major, [0, 1, 2, 3, 4, 5]);
+
+    // Gaps in the buffer
+    var.values_mapped::<i32>(&[0, 0], &[2, 3], &[1, 1], &[6, 2])
+        .unwrap_err();
+    let err = var
+        .values_mapped::<i32>(&[0, 0], &[2, 3], &[1, 1], &[1])
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::SliceLen));
+    let err = var
+        .put_values_mapped(&column_major, &[0, 0], &[2, 3], &[1, 1], &[1, 3])
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        netcdf::error::Error::BufferLen {
+            wanted: 8,
+            actual: 6
+        }
+    ));
+}
+
+#[test]
+fn error_kinds() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("error_kinds.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+    var.put_values(&[1, 2], ..).unwrap();
+    let err = var.set_compression(5, false).unwrap_err();
+    assert!(matches!(err, Error::TooLate));
+    assert_eq!(err.code(), Some(netcdf_sys::NC_ELATEDEF));
+
+    let err = var.del_attribute("units").unwrap_err();
+    assert!(err.is_not_found());
+    assert_eq!(err.code(), Some(netcdf_sys::NC_ENOTATT));
+    assert!(!err.is_type_mismatch());
+
+    let err = file.add_variable::<i32>("w", &["y"]).unwrap_err();
+    assert!(err.is_not_found());
+    assert_eq!(err.code(), None);
+
+    let err = file.add_dimension("x", 3).unwrap_err();
+    assert!(matches!(err, Error::NameInUse { .. }));
+    assert!(!err.is_permission_denied());
+}
+
+#[test]
+fn single_value_at() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("single_value_at.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    file.add_dimension("x", 3).unwrap();
+    let mut var = file.add_variable::<f64>("v", &["time", "x"]).unwrap();
+    var.put_value_at(1.5, &[0, 2]).unwrap();
+    // Grows the unlimited dimension
+    var.put_value_at(2.5, &[4, 0]).unwrap();
+    assert_eq!(var.dimensions()[0].len(), 5);
+    assert!(matches!(
+        var.put_value_at(1.0, &[0, 3]).unwrap_err(),
+        Error::IndexMismatch
+    ));
+    assert!(matches!(
+        var.put_value_at(1.0, &[0]).unwrap_err(),
+        Error::IndexLen
+    ));
+
+    assert_eq!(var.value_at::<f64>(&[0, 2]).unwrap(), 1.5);
+    assert_eq!(var.value_at::<f64>(&[4, 0]).unwrap(), 2.5);
+    assert!(matches!(
+        var.value_at::<f64>(&[5, 0]).unwrap_err(),
+        Error::IndexMismatch
+    ));
+    assert!(matches!(
+        var.value_at::<f64>(&[0, 0, 0]).unwrap_err(),
+        Error::IndexLen
+    ));
+
+    let mut scalar = file.add_variable::<i32>("scalar", &[]).unwrap();
+    scalar.put_value_at(7, &[]).unwrap();
+    assert_eq!(scalar.value_at::<i32>(&[]).unwrap(), 7);
+}
+
+#[test]
+fn bool_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("bool_values.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 4).unwrap();
+    let mut var = file.add_variable::<i8>("flags", &["x"]).unwrap();
+    var.put_values_bool(&[true, false, true, true], ..).unwrap();
+    assert_eq!(var.values::<i8, _>(..).unwrap(), [1, 0, 1, 1]);
+    var.put_values(&[-1_i8], 1).unwrap();
+    assert_eq!(var.values_bool(..).unwrap(), [true, true, true, true]);
+
+    let mut var = file.add_variable::<u8>("mask", &["x"]).unwrap();
+    var.put_values(&[0_u8, 255, 2, 0], ..).unwrap();
+    assert_eq!(var.values_bool(1..).unwrap(), [true, true, false]);
+    var.put_values_bool(&[false, true], ..2).unwrap();
+    assert_eq!(var.values::<u8, _>(..).unwrap(), [0, 1, 2, 0]);
+
+    let mut var = file.add_variable::<i32>("ints", &["x"]).unwrap();
+    assert!(matches!(
+        var.put_values_bool(&[true; 4], ..).unwrap_err(),
+        netcdf::error::Error::TypeMismatch
+    ));
+    var.values_bool(..).unwrap_err();
+}
+
+#[test]
+#[cfg(feature = "half")]
+fn half_values() {
+    use half::f16;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("half_values.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 3).unwrap();
+    assert!(matches!(
+        file.add_variable::<f16>("v", &["x"]).unwrap_err(),
+        netcdf::error::Error::Unsupported(_)
+    ));
+    assert!(file.variable("v").is_none());
+
+    let mut var = file.add_variable::<f32>("w", &["x"]).unwrap();
+    let values = [f16::from_f32(0.5), f16::MAX, f16::from_f32(-1.25)];
+    assert!(matches!(
+        var.put_values(&values, ..).unwrap_err(),
+        netcdf::error::Error::Unsupported(_)
+    ));
+    assert!(matches!(
+        var.values::<f16, _>(..).unwrap_err(),
+        netcdf::error::Error::Unsupported(_)
+    ));
+    assert!(matches!(
+        var.value::<f16, _>(0).unwrap_err(),
+        netcdf::error::Error::Unsupported(_)
+    ));
+}
+
+#[test]
+fn copy_variable_between_files() {
+    let d = tempfile::tempdir().unwrap();
+    let src_path = d.path().join("copy_src.nc");
+    let dst_path = d.path().join("copy_dst.nc");
+    let values = (0..24).map(f64::from).collect::<Vec<_>>();
+    {
+        let mut file = netcdf::create(&src_path).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        file.add_dimension("y", 3).unwrap();
+        file.add_dimension("x", 4).unwrap();
+        let mut var = file
+            .add_variable::<f64>("temp", &["time", "y", "x"])
+            .unwrap();
+        var.set_chunking(&[1, 3, 2]).unwrap();
+        var.set_compression(4, true).unwrap();
+        var.add_attribute("units", "K").unwrap();
+        var.add_attribute("valid_range", vec![0.0, 100.0]).unwrap();
+        var.put_values(&values, (..2, .., ..)).unwrap();
+        let mut var = file.add_string_variable("names", &["y"]).unwrap();
+        var.put_strings(&["a", "bc", "def"], ..).unwrap();
+        let mut var = file.add_variable::<i32>("scalar", &[]).unwrap();
+        var.put_value(7, ()).unwrap();
+        file.add_dimension("z", 5).unwrap();
+        file.add_variable::<u8>("wrong_len", &["z"]).unwrap();
+    }
+
+    let src = netcdf::open(&src_path).unwrap();
+    let mut dst = netcdf::create(&dst_path).unwrap();
+    // Reused dimension
+    dst.add_dimension("y", 3).unwrap();
+    dst.add_dimension("z", 6).unwrap();
+    dst.copy_variable_from(&src.variable("temp").unwrap(), None)
+        .unwrap();
+    dst.copy_variable_from(&src.variable("temp").unwrap(), Some("temp2"))
+        .unwrap();
+    dst.copy_variable_from(&src.variable("names").unwrap(), None)
+        .unwrap();
+    dst.copy_variable_from(&src.variable("scalar").unwrap(), None)
+        .unwrap();
+    dst.copy_variable_from(&src.variable("wrong_len").unwrap(), None)
+        .unwrap_err();
+    drop(dst);
+
+    let dst = netcdf::open(&dst_path).unwrap();
+    assert!(dst.dimension("time").unwrap().is_unlimited());
+    for name in ["temp", "temp2"] {
+        let var = dst.variable(name).unwrap();
+        assert_eq!(var.shape(), [2, 3, 4]);
+        assert_eq!(var.values::<f64, _>(..).unwrap(), values);
+        assert_eq!(var.chunking().unwrap(), Some(vec![1, 3, 2]));
+        assert_eq!(var.compression().unwrap(), Some((4, true)));
+        assert_eq!(var.attribute("units").unwrap().text().unwrap(), "K");
+        assert_eq!(
+            var.attribute("valid_range")
+                .unwrap()
+                .value_as::<Vec<f64>>()
+                .unwrap(),
+            [0.0, 100.0]
+        );
+    }
+    let var = dst.variable("names").unwrap();
+    assert_eq!(var.string_values(..).unwrap(), ["a", "bc", "def"]);
+    let var = dst.variable("scalar").unwrap();
+    assert_eq!(var.value::<i32, _>(()).unwrap(), 7);
+    assert!(dst.variable("wrong_len").is_none());
+}
+
+#[test]
+fn copy_variable_failure_leaves_data_mode() {
+    let d = tempfile::tempdir().unwrap();
+    let src_path = d.path().join("copy_failure_src.nc");
+    let dst_path = d.path().join("copy_failure_dst.nc");
+    {
+        let mut file = netcdf::create(&src_path).unwrap();
+        file.add_dimension("x", 5).unwrap();
+        file.add_dimension("y", 2).unwrap();
+        file.add_variable::<u8>("wrong_len", &["y", "x"]).unwrap();
+        file.add_variable::<i32>("v", &[]).unwrap();
+    }
+
+    let src = netcdf::open(&src_path).unwrap();
+    let mut dst = netcdf::create_with(&dst_path, netcdf::Options::CLASSIC).unwrap();
+    dst.add_dimension("x", 6).unwrap();
+    dst.add_variable::<i32>("v", &["x"]).unwrap();
+    dst.enddef().unwrap();
+
+    dst.copy_variable_from(&src.variable("wrong_len").unwrap(), None)
+        .unwrap_err();
+    let err = dst
+        .copy_variable_from(&src.variable("v").unwrap(), None)
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::NameInUse { .. }));
+    // Nothing was defined, and the file is still in data mode
+    assert!(dst.dimension("y").is_none());
+    assert_eq!(dst.variables().count(), 1);
+    let mut var = dst.variable_mut("v").unwrap();
+    var.put_values(&[1, 2, 3, 4, 5, 6], ..).unwrap();
+}
+
+#[test]
+fn read_into_vec() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("read_into_vec.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("y", 3).unwrap();
+    file.add_dimension("x", 4).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["y", "x"]).unwrap();
+    var.put_values(&(0..12).collect::<Vec<_>>(), ..).unwrap();
+
+    let mut buffer = Vec::<i32>::with_capacity(100);
+    let ptr = buffer.as_ptr();
+    var.read_into(&mut buffer, (1, ..)).unwrap();
+    assert_eq!(buffer, [4, 5, 6, 7]);
+    var.read_into(&mut buffer, (2, 1..3)).unwrap();
+    assert_eq!(buffer, [9, 10]);
+    var.read_into(&mut buffer, ..).unwrap();
+    assert_eq!(buffer, (0..12).collect::<Vec<_>>());
+    // Allocation was reused
+    assert_eq!(buffer.as_ptr(), ptr);
+
+    let mut buffer = vec![1_i64; 2];
+    var.read_into(&mut buffer, (.., 3)).unwrap();
+    assert_eq!(buffer, [3, 7, 11]);
+    var.read_into(&mut buffer, (3, ..)).unwrap_err();
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn library_version_and_features() {
+    let version = netcdf::library_version();
+    assert!(version.starts_with('4'), "{version}");
+    assert_eq!(
+        netcdf::has_feature(netcdf::Feature::Dap),
+        cfg!(feature = "has-dap")
+    );
+    assert_eq!(
+        netcdf::has_feature(netcdf::Feature::Mmap),
+        cfg!(feature = "has-mmap")
+    );
+}
+
+#[test]
+fn record_count() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("record_count.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.add_variable::<i32>("fixed", &["x"]).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["time", "x"]).unwrap();
+    assert_eq!(var.record_count().unwrap(), 0);
+    for i in 0..5 {
+        let n = var.record_count().unwrap();
+        assert_eq!(n, i);
+        var.put_values(&[i as i32, -(i as i32)], (n, ..)).unwrap();
+    }
+    assert_eq!(var.record_count().unwrap(), 5);
+
+    let var = file.variable("fixed").unwrap();
+    var.record_count().unwrap_err();
+}
+
+#[test]
+fn out_of_bounds_hyperslab() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("out_of_bounds.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    file.add_dimension("x", 4).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["time", "x"]).unwrap();
+    var.put_values(&[1, 2, 3, 4], (0, ..)).unwrap();
+
+    let err = var.put_values(&[1, 2, 3], (1, 2..5)).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::OutOfBounds {
+            dim: 1,
+            start: 2,
+            count: 3,
+            len: 4
+        }
+    ));
+    let err = var.values::<i32, _>((0, 5..)).unwrap_err();
+    assert!(matches!(err, Error::OutOfBounds { dim: 1, .. }));
+    let err = var.values::<i32, _>((0, 4)).unwrap_err();
+    assert!(matches!(err, Error::OutOfBounds { dim: 1, .. }));
+    assert!(err.to_string().contains("length 4"));
+    // The stride is taken into account for the last element
+    assert_eq!(var.values::<i32, _>((0, (1..).step_by(2))).unwrap(), [2, 4]);
+    let err = var.values::<i32, _>((0, (1..8).step_by(2))).unwrap_err();
+    assert!(matches!(err, Error::OutOfBounds { count: 3, .. }));
+    let err = var.values::<i32, _>((.., 0..4, 1)).unwrap_err();
+    assert!(matches!(err, Error::DimensionMismatch { .. }));
+
+    // Empty slices at the end and growing the unlimited dimension are fine
+    assert!(var.values::<i32, _>((0, 4..)).unwrap().is_empty());
+    var.put_values(&[5, 6, 7, 8], (3, ..)).unwrap();
+    assert_eq!(var.record_count().unwrap(), 4);
+}
+
+#[test]
+fn variable_builder() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("variable_builder.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        file.add_dimension("lat", 18).unwrap();
+        file.add_dimension("lon", 36).unwrap();
+        let mut var = file
+            .variable_builder::<f32>("temperature")
+            .dimensions(&["time", "lat", "lon"])
+            .chunking(&[1, 18, 36])
+            .compression(4, true)
+            .fill_value(-9999.0)
+            .build()
+            .unwrap();
+        var.put_values(&[1.0; 18 * 36], (1, .., ..)).unwrap();
+
+        // Invalid configurations do not leave a variable behind
+        file.variable_builder::<f32>("bad_rank")
+            .dimensions(&["time", "lat", "lon"])
+            .chunking(&[1, 18])
+            .build()
+            .unwrap_err();
+        file.variable_builder::<f32>("bad_chunk")
+            .dimensions(&["lat"])
+            .chunking(&[19])
+            .build()
+            .unwrap_err();
+        file.variable_builder::<f32>("bad_level")
+            .dimensions(&["lat"])
+            .compression(10, false)
+            .build()
+            .unwrap_err();
+        file.variable_builder::<f32>("bad_dim")
+            .dimensions(&["depth"])
+            .build()
+            .unwrap_err();
+        assert_eq!(file.variables().count(), 1);
+
+        let mut group = file.add_group("g").unwrap();
+        group
+            .variable_builder::<i32>("scalar")
+            .fill_value(7)
+            .build()
+            .unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("temperature").unwrap();
+    assert_eq!(var.chunking().unwrap(), Some(vec![1, 18, 36]));
+    assert_eq!(var.compression().unwrap(), Some((4, true)));
+    assert_eq!(var.fill_value::<f32>().unwrap(), Some(-9999.0));
+    assert_eq!(var.values::<f32, _>((0, 0, ..2)).unwrap(), [-9999.0; 2]);
+    let group = file.group("g").unwrap().unwrap();
+    let var = group.variable("scalar").unwrap();
+    assert_eq!(var.fill_value::<i32>().unwrap(), Some(7));
+
+    // Classic files are returned to data mode
+    let path = d.path().join("variable_builder_classic.nc");
+    let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.enddef().unwrap();
+    file.variable_builder::<i32>("v")
+        .dimensions(&["x"])
+        .compression(1, false)
+        .build()
+        .unwrap_err();
+    let mut var = file
+        .variable_builder::<i32>("v")
+        .dimensions(&["x"])
+        .fill_value(-1)
+        .build()
+        .unwrap();
+    var.put_values(&[1], [1]).unwrap();
+    assert_eq!(var.values::<i32, _>(..).unwrap(), [-1, 1]);
+}
+
+#[test]
+fn dimension_coordinate_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("coordinate_values.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("lat", 3).unwrap();
+        file.add_dimension("lon", 2).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        let mut var = file.add_variable::<f32>("lat", &["lat"]).unwrap();
+        var.put_values(&[-45.0, 0.0, 45.0], ..).unwrap();
+        // Not a coordinate variable, as it is not indexed by `x` alone
+        file.add_variable::<i32>("x", &["lat", "x"]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let lat = file.dimension("lat").unwrap();
+    assert_eq!(
+        lat.coordinate_values::<f32>().unwrap().unwrap(),
+        [-45.0, 0.0, 45.0]
+    );
+    assert_eq!(
+        lat.coordinate_values::<f64>().unwrap().unwrap(),
+        [-45.0, 0.0, 45.0]
+    );
+    let lon = file.dimension("lon").unwrap();
+    assert_eq!(lon.coordinate_values::<f32>().unwrap(), None);
+    let x = file.dimension("x").unwrap();
+    assert_eq!(x.coordinate_values::<i32>().unwrap(), None);
+}
+
+#[test]
+fn char_strings() {
+    use netcdf::types::{BasicType, VariableType};
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("char_strings.nc");
+    let char_type = VariableType::Basic(BasicType::Char);
+    {
+        let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+        file.add_unlimited_dimension("station").unwrap();
+        file.add_dimension("strlen", 6).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        file.add_variable_with_type("name", &["station", "strlen"], &char_type)
+            .unwrap();
+        file.add_variable_with_type("grid", &["x", "x", "strlen"], &char_type)
+            .unwrap();
+        file.add_variable::<i32>("n", &["x"]).unwrap();
+        file.enddef().unwrap();
+
+        let mut var = file.variable_mut("name").unwrap();
+        var.put_char_strings(&["Oslo", "Bergen", "Trondheim", "Tromsø", ""], 6)
+            .unwrap();
+        var.put_char_strings(&["a"], 5).unwrap_err();
+
+        let mut var = file.variable_mut("grid").unwrap();
+        var.put_char_strings(&["a", "bb", "ccc"], 6).unwrap_err();
+        var.put_char_strings(&["a", "bb", "ccc", "dddd"], 6)
+            .unwrap();
+
+        let mut var = file.variable_mut("n").unwrap();
+        let err = var.put_char_strings(&["a", "b"], 2).unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("name").unwrap();
+    // Truncated without splitting the last character
+    assert_eq!(
+        var.char_strings().unwrap(),
+        ["Oslo", "Bergen", "Trondh", "Troms", ""]
+    );
+    let var = file.variable("grid").unwrap();
+    assert_eq!(var.char_strings().unwrap(), ["a", "bb", "ccc", "dddd"]);
+    file.variable("n").unwrap().char_strings().unwrap_err();
+
+    // Strings of length zero along an unlimited dimension
+    let mut file = netcdf::create(d.path().join("char_strings_empty.nc")).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    file.add_unlimited_dimension("strlen").unwrap();
+    let var = file
+        .add_variable_with_type("blank", &["x", "strlen"], &char_type)
+        .unwrap();
+    assert_eq!(var.char_strings().unwrap(), ["", ""]);
+}
+
+#[test]
+fn classify_variables() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("classify_variables.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        file.add_dimension("lat", 2).unwrap();
+        file.add_variable::<f64>("time", &["time"]).unwrap();
+        file.add_variable::<f32>("lat", &["lat"]).unwrap();
+        file.add_variable::<f32>("t", &["time", "lat"]).unwrap();
+        file.add_variable::<f32>("lat2", &["lat"]).unwrap();
+        file.add_variable::<f32>("scalar", &[]).unwrap();
+        let mut group = file.add_group("g").unwrap();
+        group.add_variable::<f32>("lat", &["lat"]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let classify = |var: netcdf::Variable| (var.is_coordinate().unwrap(), var.is_record().unwrap());
+    assert_eq!(classify(file.variable("time").unwrap()), (true, true));
+    assert_eq!(classify(file.variable("lat").unwrap()), (true, false));
+    assert_eq!(classify(file.variable("t").unwrap()), (false, true));
+    assert_eq!(classify(file.variable("lat2").unwrap()), (false, false));
+    assert_eq!(classify(file.variable("scalar").unwrap()), (false, false));
+    // Dimension from the parent group with the same name
+    let group = file.group("g").unwrap().unwrap();
+    assert_eq!(classify(group.variable("lat").unwrap()), (true, false));
+}
+
+#[test]
+fn variable_storage() {
+    use netcdf::Storage;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("variable_storage.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_unlimited_dimension("time").unwrap();
+        file.add_dimension("x", 10).unwrap();
+        file.add_variable::<i32>("contiguous", &["x"]).unwrap();
+        let mut var = file.add_variable::<i32>("chunked", &["x"]).unwrap();
+        var.set_chunking(&[5]).unwrap();
+        file.add_variable::<i32>("record", &["time", "x"]).unwrap();
+        file.add_variable::<i32>("scalar", &[]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let storage = |name| file.variable(name).unwrap().storage().unwrap();
+    assert_eq!(storage("contiguous"), Storage::Contiguous);
+    assert_eq!(storage("chunked"), Storage::Chunked(vec![5]));
+    // Unlimited dimensions require chunking
+    assert!(matches!(storage("record"), Storage::Chunked(c) if c.len() == 2));
+    assert!(matches!(
+        storage("scalar"),
+        Storage::Contiguous | Storage::Compact
+    ));
+
+    let path = d.path().join("variable_storage_classic.nc");
+    let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+    file.add_dimension("x", 10).unwrap();
+    let var = file.add_variable::<i32>("v", &["x"]).unwrap();
+    assert_eq!(var.storage().unwrap(), Storage::Contiguous);
+}
+
+#[test]
+fn add_conflicting_groups() {
+    let d = tempfile::tempdir().unwrap();
+    let mut file = netcdf::create(d.path().join("conflict_group.nc")).unwrap();
+
+    file.add_group("g").unwrap();
+    let e = file.add_group("g").unwrap_err();
+    assert!(matches!(
+        e,
+        netcdf::error::Error::NameInUse { kind: "group", ref name } if name == "g"
+    ));
+    assert_eq!(e.to_string(), "group g already exists");
+    // Groups and variables share names within a group
+    file.add_dimension("x", 1).unwrap();
+    let e = file.add_variable::<i32>("g", &["x"]).unwrap_err();
+    assert!(matches!(
+        e,
+        netcdf::error::Error::NameInUse {
+            kind: "variable",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn raw_bytes_in_byte_order() {
+    use netcdf::Endianness;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("raw_bytes.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 3).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["x"]).unwrap();
+    // The byte order on disk does not affect the output
+    var.set_endianness(Endianness::Big).unwrap();
+    var.put_values(&[1, 0x0102_0304, -1], ..).unwrap();
+    let mut var = file.add_variable::<u8>("b", &["x"]).unwrap();
+    var.put_values(&[1_u8, 2, 3], ..).unwrap();
+    file.add_string_variable("s", &["x"]).unwrap();
+
+    let var = file.variable("v").unwrap();
+    let little = var.raw_bytes(1..3, Endianness::Little).unwrap();
+    assert_eq!(little, [4, 3, 2, 1, 0xff, 0xff, 0xff, 0xff]);
+    let big = var.raw_bytes(..2, Endianness::Big).unwrap();
+    assert_eq!(big, [0, 0, 0, 1, 1, 2, 3, 4]);
+    let native = var.raw_bytes(1, Endianness::Native).unwrap();
+    assert_eq!(native, 0x0102_0304_i32.to_ne_bytes());
+
+    let var = file.variable("b").unwrap();
+    assert_eq!(var.raw_bytes(.., Endianness::Big).unwrap(), [1, 2, 3]);
+    let var = file.variable("s").unwrap();
+    let err = var.raw_bytes(.., Endianness::Little).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+}
+
+#[test]
+fn masked_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("masked_values.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("x", 5).unwrap();
+        let mut var = file.add_variable::<i16>("counts", &["x"]).unwrap();
+        var.set_fill_value(-1_i16).unwrap();
+        var.add_attribute("missing_value", vec![-2_i16, -3])
+            .unwrap();
+        var.put_values(&[1_i16, -2, 3, -3], ..4).unwrap();
+
+        let mut var = file.add_variable::<f32>("plain", &["x"]).unwrap();
+        var.put_values(&[1.0_f32, 2.0], ..2).unwrap();
+
+        let mut var = file.add_variable::<u8>("mismatch", &["x"]).unwrap();
+        var.add_attribute("missing_value", 255.0_f64).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("counts").unwrap();
+    assert_eq!(
+        var.values_masked::<i16, _>(..).unwrap(),
+        [Some(1), None, Some(3), None, None]
+    );
+    assert_eq!(var.values_masked::<i16, _>(2..3).unwrap(), [Some(3)]);
+    let err = var.values_masked::<i32, _>(..).unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+
+    // The default fill value is kept without a `_FillValue` attribute
+    let var = file.variable("plain").unwrap();
+    let values = var.values_masked::<f32, _>(..).unwrap();
+    assert_eq!(&values[..2], [Some(1.0), Some(2.0)]);
+    assert!(values[2..].iter().all(Option::is_some));
+
+    let var = file.variable("mismatch").unwrap();
+    var.values_masked::<u8, _>(..).unwrap_err();
+}
+
+#[test]
+fn put_values_from_iterator() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("put_values_from.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    file.add_dimension("x", 3).unwrap();
+    let mut var = file.add_variable::<i32>("v", &["time", "x"]).unwrap();
+    var.put_values_from((0..6).map(|i| i * 10), (0..2, ..))
+        .unwrap();
+    // Infinite iterators are cut after the requested elements
+    var.put_values_from(std::iter::repeat(7), (2, ..))
+        .unwrap_err();
+    let err = var.put_values_from(0..2, (2, ..)).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::BufferLen {
+            wanted: 3,
+            actual: 2
+        }
+    ));
+    var.put_values_from(vec![1, 2, 3], (2, ..)).unwrap();
+    var.put_values_from([5, 6], (3, (0..).step_by(2))).unwrap();
+
+    assert_eq!(var.record_count().unwrap(), 4);
+    let values = var.values::<i32, _>(..).unwrap();
+    assert_eq!(&values[..9], [0, 10, 20, 30, 40, 50, 1, 2, 3]);
+    assert_eq!(values[9], 5);
+    assert_eq!(values[11], 6);
+}
+
+#[test]
+#[cfg(feature = "time")]
+fn time_values() {
+    use chrono::{TimeZone, Utc};
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("time_values.nc");
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("time", 3).unwrap();
+        let mut var = file.add_variable::<f64>("time", &["time"]).unwrap();
+        var.add_attribute("units", "hours since 2000-01-01 00:00:00")
+            .unwrap();
+        var.put_values(&[0.0, 1.5, 48.0], ..).unwrap();
+
+        let mut var = file.add_variable::<i32>("days", &["time"]).unwrap();
+        var.add_attribute("units", "days since 1999-12-31T12:00:00Z")
+            .unwrap();
+        var.add_attribute("calendar", "proleptic_gregorian")
+            .unwrap();
+        var.put_values(&[0, 1, -1], ..).unwrap();
+
+        let mut var = file.add_variable::<f64>("climate", &["time"]).unwrap();
+        var.add_attribute("units", "days since 2000-01-01").unwrap();
+        var.add_attribute("calendar", "360_day").unwrap();
+
+        let mut var = file.add_variable::<f64>("nounits", &["time"]).unwrap();
+        var.add_attribute("units", "hours").unwrap();
+
+        let mut var = file.add_variable::<f64>("offset", &["time"]).unwrap();
+        var.add_attribute("units", "days since 2000-01-01 00:00 +1000000000000000")
+            .unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("time").unwrap();
+    assert_eq!(
+        var.time_values().unwrap(),
+        [
+            Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2000, 1, 1, 1, 30, 0).unwrap(),
+            Utc.with_ymd_and_hms(2000, 1, 3, 0, 0, 0).unwrap(),
+        ]
+    );
+    let var = file.variable("days").unwrap();
+    assert_eq!(
+        var.time_values().unwrap(),
+        [
+            Utc.with_ymd_and_hms(1999, 12, 31, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(1999, 12, 30, 12, 0, 0).unwrap(),
+        ]
+    );
+    let err = file.variable("climate").unwrap().time_values().unwrap_err();
+    assert!(err.to_string().contains("360_day"));
+    file.variable("nounits").unwrap().time_values().unwrap_err();
+    let err = file.variable("offset").unwrap().time_values().unwrap_err();
+    assert!(err.to_string().contains("invalid time units"));
+}
+
+#[test]
+fn default_compression() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("default_compression.nc");
+    let mut file = netcdf::create_with(&path, netcdf::Options::NETCDF4).unwrap();
+    file.add_dimension("x", 10).unwrap();
+    file.add_variable::<f64>("before", &["x"]).unwrap();
+    file.set_default_compression(10, false).unwrap_err();
+    file.set_default_compression(4, true).unwrap();
+
+    file.add_variable::<f64>("plain", &["x"]).unwrap();
+    file.add_variable::<i32>("scalar", &[]).unwrap();
+    file.add_string_variable("strings", &["x"]).unwrap();
+    let mut var = file.add_variable::<f32>("override", &["x"]).unwrap();
+    var.set_compression(9, true).unwrap();
+    file.variable_builder::<u8>("built")
+        .dimensions(&["x"])
+        .build()
+        .unwrap();
+    file.variable_builder::<u8>("built_override")
+        .dimensions(&["x"])
+        .compression(1, false)
+        .build()
+        .unwrap();
+    file.define(|def| def.variable::<i16>("defined", &["x"]).map(|_| ()))
+        .unwrap();
+
+    let compression = |name| file.variable(name).unwrap().compression().unwrap();
+    assert_eq!(compression("before"), None);
+    assert_eq!(compression("plain"), Some((4, true)));
+    assert_eq!(compression("scalar"), None);
+    assert_eq!(compression("strings"), None);
+    assert_eq!(compression("override"), Some((9, true)));
+    assert_eq!(compression("built"), Some((4, true)));
+    assert_eq!(compression("built_override"), Some((1, false)));
+    assert_eq!(compression("defined"), Some((4, true)));
+
+    // Also applies to subgroups
+    let mut group = file.add_group("g").unwrap();
+    group.add_variable::<f64>("v", &["x"]).unwrap();
+    let mut sub = group.add_group("sub").unwrap();
+    sub.variable_builder::<f64>("built")
+        .dimensions(&["x"])
+        .build()
+        .unwrap();
+    let mut group = file.group_mut("g").unwrap().unwrap();
+    group.add_variable::<f64>("w", &["x"]).unwrap();
+    let group = file.group("g").unwrap().unwrap();
+    for var in ["v", "w"] {
+        let var = group.variable(var).unwrap();
+        assert_eq!(var.compression().unwrap(), Some((4, true)));
+    }
+    let sub = group.group("sub").unwrap();
+    let built = sub.variable("built").unwrap();
+    assert_eq!(built.compression().unwrap(), Some((4, true)));
+
+    file.set_default_compression(0, false).unwrap();
+    file.add_variable::<f64>("after", &["x"]).unwrap();
+    assert_eq!(file.variable("after").unwrap().compression().unwrap(), None);
+
+    let path = d.path().join("default_compression_classic.nc");
+    let mut file = netcdf::create_with(&path, netcdf::Options::CLASSIC).unwrap();
+    file.set_default_compression(4, true).unwrap_err();
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn values_arr_order() {
+    use netcdf::Order;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("values_arr_order.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("y", 3).unwrap();
+    file.add_dimension("x", 4).unwrap();
+    let mut var = file.add_variable::<i32>("grid", &["y", "x"]).unwrap();
+    var.put_values(&(0..12).collect::<Vec<i32>>(), ..).unwrap();
+
+    let rows = var.values_arr::<i32, _>(..).unwrap();
+    let same = var.values_arr_order::<i32, _>(.., Order::RowMajor).unwrap();
+    assert_eq!(rows, same);
+    assert!(same.is_standard_layout());
+
+    let columns = var
+        .values_arr_order::<i32, _>(.., Order::ColumnMajor)
+        .unwrap();
+    assert_eq!(columns, rows);
+    assert!(columns.t().is_standard_layout());
+    assert_eq!(
+        columns.as_slice_memory_order().unwrap(),
+        [0, 4, 8, 1, 5, 9, 2, 6, 10, 3, 7, 11]
+    );
+
+    let columns = var
+        .values_arr_order::<i32, _>((1.., (0..).step_by(2)), Order::ColumnMajor)
+        .unwrap();
+    assert_eq!(columns.shape(), [2, 2]);
+    assert_eq!(columns.as_slice_memory_order().unwrap(), [4, 8, 6, 10]);
+
+    let empty = var
+        .values_arr_order::<i32, _>((.., 0..0), Order::ColumnMajor)
+        .unwrap();
+    assert_eq!(empty.shape(), [3, 0]);
+}
+
+#[test]
+fn dimension_names() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("dimension_names.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    file.add_dimension("lat", 2).unwrap();
+    file.add_dimension("lon", 3).unwrap();
+    file.add_variable::<f32>("temp", &["time", "lat", "lon"])
+        .unwrap();
+    file.add_variable::<f32>("scalar", &[]).unwrap();
+    let mut group = file.add_group("g").unwrap();
+    group.add_dimension("level", 4).unwrap();
+    group
+        .add_variable::<f32>("inner", &["level", "lon"])
+        .unwrap();
+
+    let var = file.variable("temp").unwrap();
+    assert_eq!(var.dimension_names().unwrap(), ["time", "lat", "lon"]);
+    assert!(file
+        .variable("scalar")
+        .unwrap()
+        .dimension_names()
+        .unwrap()
+        .is_empty());
+    let group = file.group("g").unwrap().unwrap();
+    let var = group.variable("inner").unwrap();
+    assert_eq!(var.dimension_names().unwrap(), ["level", "lon"]);
+}
+
+#[test]
+fn put_raw_bytes_in_byte_order() {
+    use netcdf::Endianness;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("put_raw_bytes.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 4).unwrap();
+    let mut var = file.add_variable::<f32>("v", &["x"]).unwrap();
+    // The file stores big endian values, the input is little endian
+    var.set_endianness(Endianness::Big).unwrap();
+    let little = [1.5_f32, -2.0, 1e-3]
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect::<Vec<u8>>();
+    var.put_raw_bytes(&little, ..3, Endianness::Little).unwrap();
+    var.put_raw_bytes(&7.25_f32.to_be_bytes(), 3, Endianness::Big)
+        .unwrap();
+    assert_eq!(var.values::<f32, _>(..).unwrap(), [1.5, -2.0, 1e-3, 7.25]);
+    assert_eq!(var.raw_bytes(..3, Endianness::Little).unwrap(), little);
+
+    let err = var
+        .put_raw_bytes(&little[..8], ..3, Endianness::Native)
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::BufferLen { .. }));
+    let mut var = file.add_string_variable("s", &["x"]).unwrap();
+    let err = var
+        .put_raw_bytes(&[0; 8], ..1, Endianness::Native)
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+}
+
+#[test]
+fn put_all() {
+    use netcdf::error::Error;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("put_all.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("lat", 2).unwrap();
+    file.add_dimension("lon", 3).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+
+    let mut var = file.add_variable::<i32>("grid", &["lat", "lon"]).unwrap();
+    var.put_all(&[1, 2, 3, 4, 5, 6]).unwrap();
+    assert_eq!(var.values_all::<i32>().unwrap(), [1, 2, 3, 4, 5, 6]);
+    let err = var.put_all(&[1, 2, 3]).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::BufferLen {
+            wanted: 6,
+            actual: 3
+        }
+    ));
+
+    let mut var = file.add_variable::<f64>("scalar", &[]).unwrap();
+    var.put_all(&[2.5]).unwrap();
+    assert_eq!(var.values_all::<f64>().unwrap(), [2.5]);
+
+    let mut var = file
+        .add_variable::<i32>("series", &["time", "lon"])
+        .unwrap();
+    let err = var.put_all(&[1, 2, 3]).unwrap_err();
+    assert!(err.to_string().contains("time"));
+}
+
+#[test]
+#[cfg(feature = "complex")]
+fn complex_values() {
+    use netcdf::types::VariableType;
+    use num_complex::Complex;
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("complex_values.nc");
+    let values = [
+        Complex::new(1.0, -1.0),
+        Complex::new(0.5, 2.0),
+        Complex::new(-3.0, 0.25),
+    ];
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_dimension("time", 3).unwrap();
+        file.add_dimension("complex", 2).unwrap();
+
+        let mut var = file
+            .add_variable::<f64>("pairs", &["time", "complex"])
+            .unwrap();
+        var.put_complex_values(&values, ..).unwrap();
+
+        for (name, single) in [("c64", true), ("c128", false)] {
+            let mut builder = file.add_compound_type(&format!("{name}_t")).unwrap();
+            if single {
+                builder.add::<f32>("r").unwrap().add::<f32>("i").unwrap();
+            } else {
+                builder.add::<f64>("r").unwrap().add::<f64>("i").unwrap();
+            }
+            let typ = builder.build().unwrap();
+            let mut var = file
+                .add_variable_with_type(name, &["time"], &VariableType::Compound(typ))
+                .unwrap();
+            var.put_complex_values(&values[..2], ..2).unwrap();
+            var.put_complex_values(&values[2..], 2..).unwrap();
+        }
+
+        let mut builder = file.add_compound_type("other_t").unwrap();
+        builder.add::<f64>("x").unwrap().add::<f64>("y").unwrap();
+        let typ = builder.build().unwrap();
+        file.add_variable_with_type("other", &["time"], &VariableType::Compound(typ))
+            .unwrap();
+        file.add_variable::<f64>("plain", &["time"]).unwrap();
+    }
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("pairs").unwrap();
+    assert_eq!(var.complex_values(..).unwrap(), values);
+    assert_eq!(var.complex_values(1..).unwrap(), values[1..]);
+    assert_eq!(
+        var.values::<f64, _>((1, ..)).unwrap(),
+        [values[1].re, values[1].im]
+    );
+    for name in ["c64", "c128"] {
+        let var = file.variable(name).unwrap();
+        assert_eq!(var.complex_values(..).unwrap(), values);
+        assert_eq!(var.complex_values(2).unwrap(), values[2..]);
+    }
+    file.variable("other")
+        .unwrap()
+        .complex_values(..)
+        .unwrap_err();
+    file.variable("plain")
+        .unwrap()
+        .complex_values(..)
+        .unwrap_err();
+}
+
+#[test]
+fn chunk_iter() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("chunk_iter.nc");
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("y", 5).unwrap();
+    file.add_dimension("x", 7).unwrap();
+    file.add_unlimited_dimension("time").unwrap();
+    let data = (0..35).collect::<Vec<i32>>();
+
+    let mut var = file.add_variable::<i32>("chunked", &["y", "x"]).unwrap();
+    var.set_chunking(&[2, 3]).unwrap();
+    var.put_values(&data, ..).unwrap();
+    let chunks = var
+        .chunk_iter::<i32>()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let starts = chunks.iter().map(|(s, _)| s.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        starts,
+        [
+            [0, 0],
+            [0, 3],
+            [0, 6],
+            [2, 0],
+            [2, 3],
+            [2, 6],
+            [4, 0],
+            [4, 3],
+            [4, 6]
+        ]
+    );
+    assert_eq!(chunks[0].1, [0, 1, 2, 7, 8, 9]);
+    assert_eq!(chunks[2].1, [6, 13]);
+    assert_eq!(chunks[8].1, [34]);
+    let mut rebuilt = vec![0; 35];
+    for (start, values) in &chunks {
+        let width = if start[1] == 6 { 1 } else { 3 };
+        for (k, &v) in values.iter().enumerate() {
+            rebuilt[(start[0] + k / width) * 7 + start[1] + k % width] = v;
+        }
+    }
+    assert_eq!(rebuilt, data);
+
+    let mut var = file.add_variable::<i32>("contiguous", &["y", "x"]).unwrap();
+    var.put_values(&data, ..).unwrap();
+    let mut var = file.add_variable::<f64>("scalar", &[]).unwrap();
+    var.put_value(1.5, ()).unwrap();
+    file.add_variable::<u8>("empty", &["time", "x"]).unwrap();
+
+    let var = file.variable("contiguous").unwrap();
+    assert_eq!(var.storage().unwrap(), netcdf::Storage::Contiguous);
+    let chunks = var.chunk_iter::<i32>().unwrap().collect::<Vec<_>>();
+    assert_eq!(chunks.len(), 1);
+    let (start, values) = chunks.into_iter().next().unwrap().unwrap();
+    assert_eq!(start, [0, 0]);
+    assert_eq!(values, data);
+    let var = file.variable("scalar").unwrap();
+    let chunks = var.chunk_iter::<f64>().unwrap().collect::<Vec<_>>();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].as_ref().unwrap(), &(vec![], vec![1.5]));
+    let var = file.variable("empty").unwrap();
+    assert_eq!(var.chunk_iter::<u8>().unwrap().count(), 0);
+}
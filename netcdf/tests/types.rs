@@ -320,3 +320,262 @@ fn char() {
     var.raw_values(&mut retrieved_vals, 0..2).unwrap();
     assert_eq!(vals, retrieved_vals);
 }
+
+#[test]
+fn put_get_vlens() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("test_put_get_vlens.nc");
+
+    let readings = vec![vec![1.5_f32, 2.5], vec![], vec![3.0, 4.0, 5.0, 6.0]];
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_unlimited_dimension("record").unwrap();
+        let typ = file.add_vlen_type::<f32>("readings").unwrap();
+        let mut var = file
+            .add_variable_with_type("sensor", &["record"], &typ.into())
+            .unwrap();
+
+        var.put_vlens(&readings, ..3).unwrap();
+        var.put_vlens(&[[7.0_f32]], 3..4).unwrap();
+        assert!(matches!(
+            var.put_vlens(&readings, 0..2).unwrap_err(),
+            netcdf::error::Error::BufferLen {
+                wanted: 2,
+                actual: 3
+            }
+        ));
+        var.put_vlens(&[[1_i32]], 0..1).unwrap_err();
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("sensor").unwrap();
+    let values = var.vlens::<f32, _>(..).unwrap();
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[..3], readings);
+    assert_eq!(values[3], [7.0]);
+    assert_eq!(var.vlens::<f32, _>(1..3).unwrap(), &readings[1..]);
+    var.vlens::<f64, _>(..).unwrap_err();
+}
+
+#[test]
+fn put_get_compound_raw() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("test_put_get_compound_raw.nc");
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        let mut builder = file.add_compound_type("obs_t").unwrap();
+        builder.add::<u8>("flag").unwrap();
+        builder.add::<i32>("count").unwrap();
+        builder.add::<f64>("value").unwrap();
+        let typ = builder.build().unwrap();
+        assert_eq!(typ.size(), 1 + 4 + 8);
+
+        file.add_dimension("n", 3).unwrap();
+        let mut var = file
+            .add_variable_with_type("obs", &["n"], &typ.into())
+            .unwrap();
+
+        let mut raw = Vec::new();
+        for i in 0..3_u8 {
+            raw.push(i);
+            raw.extend_from_slice(&(i32::from(i) * 10).to_ne_bytes());
+            raw.extend_from_slice(&(f64::from(i) + 0.5).to_ne_bytes());
+        }
+        unsafe { var.put_raw_values(&raw, ..).unwrap() };
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("obs").unwrap();
+    let (bytes, typ) = var.values_compound_raw(..).unwrap();
+    assert_eq!(bytes.len(), 3 * typ.size());
+
+    let fields = typ.fields().collect::<Vec<_>>();
+    let names = fields.iter().map(|f| f.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["flag", "count", "value"]);
+    assert!(fields[1].typ().is_i32());
+    assert!(fields[2].typ().is_f64());
+
+    for (i, element) in bytes.chunks_exact(typ.size()).enumerate() {
+        let count = &element[fields[1].offset()..][..4];
+        let value = &element[fields[2].offset()..][..8];
+        assert_eq!(usize::from(element[fields[0].offset()]), i);
+        assert_eq!(i32::from_ne_bytes(count.try_into().unwrap()), i as i32 * 10);
+        assert_eq!(
+            f64::from_ne_bytes(value.try_into().unwrap()),
+            i as f64 + 0.5
+        );
+    }
+
+    let (bytes, _) = var.values_compound_raw(1..2).unwrap();
+    assert_eq!(bytes[0], 1);
+
+    let path = d.path().join("test_not_compound.nc");
+    let mut file = netcdf::create(path).unwrap();
+    let var = file.add_variable::<i32>("v", &[]).unwrap();
+    var.values_compound_raw(()).unwrap_err();
+}
+
+#[test]
+fn enum_member_names() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("test_enum_member_names.nc");
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        let e = file
+            .add_enum_type("quality_t", &[("good", 0_i16), ("suspect", 1), ("bad", 4)])
+            .unwrap();
+        file.add_dimension("x", 3).unwrap();
+        let mut var = file
+            .add_variable_with_type("quality", &["x"], &e.into())
+            .unwrap();
+        let raw = [4_i16, 0, 2]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect::<Vec<u8>>();
+        unsafe { var.put_raw_values(&raw, ..).unwrap() };
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("quality").unwrap();
+    let mut raw = [0_u8; 3 * 2];
+    var.raw_values(&mut raw, ..).unwrap();
+    let names = raw
+        .chunks_exact(2)
+        .map(|flag| i16::from_ne_bytes([flag[0], flag[1]]))
+        .map(|flag| var.enum_member_name(flag).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [Some("bad".to_owned()), Some("good".to_owned()), None]
+    );
+    var.enum_member_name(1_i32).unwrap_err();
+
+    let path = d.path().join("test_enum_member_names_basic.nc");
+    let mut file = netcdf::create(path).unwrap();
+    let var = file.add_variable::<i16>("v", &[]).unwrap();
+    var.enum_member_name(1_i16).unwrap_err();
+}
+
+#[test]
+fn variable_types() {
+    use netcdf::types::{BasicType, VariableType};
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("test_variable_types.nc");
+
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        file.add_variable::<i16>("short", &[]).unwrap();
+        file.add_variable::<f64>("double", &[]).unwrap();
+        file.add_string_variable("string", &[]).unwrap();
+        let vlen = file.add_vlen_type::<u8>("vlen_t").unwrap();
+        file.add_variable_with_type("vlen", &[], &vlen.into())
+            .unwrap();
+        let opaque = file.add_opaque_type("opaque_t", 4).unwrap();
+        file.add_variable_with_type("opaque", &[], &opaque.into())
+            .unwrap();
+        let e = file.add_enum_type("enum_t", &[("a", 1_i8)]).unwrap();
+        file.add_variable_with_type("enum", &[], &e.into()).unwrap();
+        let mut builder = file.add_compound_type("compound_t").unwrap();
+        builder.add::<i32>("i").unwrap();
+        let compound = builder.build().unwrap();
+        file.add_variable_with_type("compound", &[], &compound.into())
+            .unwrap();
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let typ = |name| file.variable(name).unwrap().vartype();
+    assert!(matches!(
+        typ("short"),
+        VariableType::Basic(BasicType::Short)
+    ));
+    assert!(typ("double").is_f64());
+    assert!(typ("string").is_string());
+    match typ("vlen") {
+        VariableType::Vlen(v) => assert!(v.typ().is_u8()),
+        t => panic!("unexpected type {}", t.name()),
+    }
+    assert!(matches!(typ("opaque"), VariableType::Opaque(_)));
+    match typ("enum") {
+        VariableType::Enum(e) => assert_eq!(e.name(), "enum_t"),
+        t => panic!("unexpected type {}", t.name()),
+    }
+    match typ("compound") {
+        VariableType::Compound(c) => assert_eq!(c.fields().count(), 1),
+        t => panic!("unexpected type {}", t.name()),
+    }
+}
+
+#[test]
+fn put_get_opaque_values() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("put_get_opaque_values.nc");
+    let blobs = vec![[1_u8, 2, 3], [4, 5, 6]];
+    {
+        let mut file = netcdf::create(&path).unwrap();
+        let typ = file.add_opaque_type("blob_t", 3).unwrap();
+        file.add_dimension("x", 2).unwrap();
+        let mut var = file
+            .add_variable_with_type("blobs", &["x"], &typ.into())
+            .unwrap();
+        var.put_opaque_values(&blobs, ..).unwrap();
+
+        let err = var.put_opaque_values(&[vec![1_u8, 2]], 0).unwrap_err();
+        assert!(matches!(
+            err,
+            netcdf::error::Error::BufferLen {
+                wanted: 3,
+                actual: 2
+            }
+        ));
+        let mut var = file.add_variable::<u8>("bytes", &["x"]).unwrap();
+        let err = var.put_opaque_values(&blobs, ..).unwrap_err();
+        assert!(matches!(err, netcdf::error::Error::TypeMismatch));
+    }
+
+    let file = netcdf::open(&path).unwrap();
+    let var = file.variable("blobs").unwrap();
+    assert_eq!(
+        var.opaque_values(..).unwrap(),
+        [vec![1, 2, 3], vec![4, 5, 6]]
+    );
+    assert_eq!(var.opaque_values(1..).unwrap(), [vec![4, 5, 6]]);
+    file.variable("bytes")
+        .unwrap()
+        .opaque_values(..)
+        .unwrap_err();
+}
+
+#[test]
+fn add_variable_with_type_id() {
+    let d = tempfile::tempdir().unwrap();
+    let path = d.path().join("add_variable_with_type_id.nc");
+
+    let mut file = netcdf::create(&path).unwrap();
+    file.add_dimension("x", 2).unwrap();
+    let typ = file
+        .add_enum_type::<u8>("flag_t", &[("a", 0), ("b", 1)])
+        .unwrap();
+    let var = file
+        .add_variable_with_type_id("flags", &["x"], netcdf::types::VariableType::from(typ).id())
+        .unwrap();
+    assert!(matches!(
+        var.vartype(),
+        netcdf::types::VariableType::Enum(_)
+    ));
+    let mut var = file
+        .add_variable_with_type_id("names", &["x"], netcdf_sys::NC_STRING)
+        .unwrap();
+    var.put_strings(&["a", "b"], ..).unwrap();
+    let mut g = file.add_group("g").unwrap();
+    let var = g
+        .add_variable_with_type_id("f", &["x"], netcdf_sys::NC_FLOAT)
+        .unwrap();
+    assert!(var.vartype().is_f32());
+
+    let err = file
+        .add_variable_with_type_id("unknown", &["x"], 12345)
+        .unwrap_err();
+    assert!(matches!(err, netcdf::error::Error::TypeUnknown(12345)));
+}